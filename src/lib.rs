@@ -72,11 +72,20 @@
 
 use std::{
     any::{Any, TypeId},
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    hash::{Hash, Hasher},
     marker::PhantomData,
-    ops::Deref,
-    sync::{Arc, LazyLock},
+    ops::{Bound, Deref},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc, LazyLock,
+    },
 };
 
+use itertools::Itertools;
+
 #[doc(hidden)]
 pub use itertools;
 #[doc(hidden)]
@@ -100,6 +109,14 @@ pub trait Store: Sized {
     /// The type used to sort the implementations (e.g., `u64`).
     type Ordering: Ord + Clone;
 
+    /// How implementations sharing a [name](Entry::name) are resolved,
+    /// set by the `name_policy: ..;` option of `create_stain! {...}`.
+    const NAME_POLICY: NamePolicy = NamePolicy::First;
+
+    /// Which registration is kept when a type is stained more than once,
+    /// set by the `dedup_policy: ..;` option of `create_stain! {...}`.
+    const DEDUP_POLICY: DedupPolicy = DedupPolicy::KeepLast;
+
     /// Collects all distributed implementations linked into the binary.
     ///
     /// This operation allocates the internal storage and sorts the entries
@@ -111,15 +128,255 @@ pub trait Store: Sized {
     /// ```
     fn collect() -> Self;
 
+    /// Re-collects all distributed implementations into an existing store.
+    ///
+    /// The result is the same as [collect](Store::collect), but the
+    /// store's existing allocations are reused where possible, which is
+    /// useful when a store is rebuilt frequently (e.g. on config reloads).
+    ///
+    /// # Example
+    /// ```ignore
+    /// store.collect_into();
+    /// ```
+    fn collect_into(&mut self);
+
+    /// Collects only the distributed implementations for which the predicate
+    /// returns `true`.
+    ///
+    /// The predicate only sees the static [Entry] metadata (name, ordering,
+    /// etc.), so filtered out implementations are never constructed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let store = DiscoverStore::collect_filtered(|entry| *entry.ordering() < 10);
+    /// ```
+    fn collect_filtered<F>(f: F) -> Self
+    where
+        F: Fn(&Entry<Self::Ordering, Self::Item>) -> bool;
+
+    /// Adds the implementations in `other` to this store, leaving `other`
+    /// as is.
+    ///
+    /// Useful for combining stores that were [filtered](Store::collect_filtered)
+    /// or [retained](Store::retain) separately. A type in both stores is
+    /// kept once, following the store's [DedupPolicy].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut store = DiscoverStore::collect_filtered(|entry| entry.meta().is_empty());
+    /// store.extend_with_store(&optional_features);
+    /// ```
+    fn extend_with_store(&mut self, other: &Self);
+
     /// Returns an iterator over all collected implementations, sorted by order.
+    ///
+    /// Implementations sharing an ordering are yielded by
+    /// [tiebreak](Entry::tiebreak), then by [name](Entry::name), then by
+    /// [TypeId], so the iteration order is the same across runs.
+    ///
+    /// Iterating doesn't construct implementations: an [EntryRef]'s
+    /// metadata (name, ordering, etc.) is static, and an implementation
+    /// is only constructed when the entry is first dereferenced to it.
     fn iter(&self) -> impl Iterator<Item = EntryRef<'_, Self::Ordering, Self::Item>>;
 
+    /// Returns an iterator over all collected implementations, highest ordering first.
+    ///
+    /// Only the orderings are walked in reverse: implementations sharing
+    /// an ordering are yielded in the same order as [iter](Store::iter).
+    fn iter_desc(&self) -> impl Iterator<Item = EntryRef<'_, Self::Ordering, Self::Item>>;
+
+    /// Returns an iterator over all collected implementations, sorted by
+    /// a key computed from each implementation.
+    ///
+    /// This is for implementations that know their own priority, e.g. through
+    /// a trait method, rather than having it fixed at registration. Ties
+    /// keep the order of [iter](Store::iter).
+    ///
+    /// *Note:* Computing the keys initializes every implementation.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for discover in store.iter_by(|discover| discover.priority()) {
+    ///     // ...
+    /// }
+    /// ```
+    fn iter_by<K, F>(
+        &self,
+        mut f: F,
+    ) -> impl Iterator<Item = EntryRef<'_, Self::Ordering, Self::Item>>
+    where
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.iter().sorted_by_cached_key(|entry| f(entry.0.deref()))
+    }
+
+    /// Returns an iterator over all collected implementations, in the order
+    /// they were stained.
+    ///
+    /// The linker doesn't guarantee any order, so this sorts by the
+    /// [Location] of each `stain! {...}` invocation. Useful for debugging.
+    ///
+    /// *Note:* That's definition order within a file only. Across files,
+    /// implementations are ordered alphabetically by file path, not by
+    /// where the files are declared. There's no per-entry registration
+    /// index, as a counter bumped while constructing implementations
+    /// would record construction order instead.
+    fn iter_registration_order(
+        &self,
+    ) -> impl Iterator<Item = EntryRef<'_, Self::Ordering, Self::Item>> {
+        self.iter().sorted_by_key(|entry| entry.location())
+    }
+
+    /// Returns an iterator over all collected implementations, sorted by
+    /// order, each paired with its [name](Entry::name).
+    ///
+    /// # Example
+    /// ```ignore
+    /// for (name, plugin) in store.iter_named() {
+    ///     log::info!("running {name}");
+    ///     plugin.run();
+    /// }
+    /// ```
+    fn iter_named(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, EntryRef<'_, Self::Ordering, Self::Item>)> {
+        self.iter().map(|entry| (entry.name(), entry))
+    }
+
+    /// Returns all collected implementations as a slice, sorted like
+    /// [iter](Store::iter).
+    ///
+    /// This allows indexed access and binary searches by ordering.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let entries = store.entries_slice();
+    /// let found = entries.binary_search_by_key(&&3, |entry| entry.ordering());
+    /// ```
+    fn entries_slice(&self) -> &[EntryRef<'_, Self::Ordering, Self::Item>];
+
+    /// Returns each ordering along with its implementations, as a slice
+    /// of [entries_slice](Store::entries_slice), in ascending order.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for (ordering, tier) in store.tiers() {
+    ///     println!("{ordering}: {} plugin(s)", tier.len());
+    /// }
+    /// ```
+    fn tiers(&self) -> impl Iterator<Item = Tier<'_, Self::Ordering, Self::Item>> {
+        self.entries_slice()
+            .chunk_by(|a, b| a.ordering() == b.ordering())
+            .map(|tier| (tier[0].0.ordering(), tier))
+    }
+
+    /// Returns the lowest ordering that has implementations, along with
+    /// them, for falling back to the best available tier.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Some((_, handlers)) = store.top_non_empty() {
+    ///     handlers.iter().for_each(|handler| handler.handle(&request));
+    /// }
+    /// ```
+    fn top_non_empty(&self) -> Option<Tier<'_, Self::Ordering, Self::Item>> {
+        self.tiers().next()
+    }
+
+    /// Returns an iterator over the [unordered](Entry::is_unordered)
+    /// implementations, which a runner may execute concurrently.
+    ///
+    /// [iter](Store::iter) still yields them at their ordering, so
+    /// sequential runners see the same deterministic order as always.
+    fn unordered(&self) -> impl Iterator<Item = EntryRef<'_, Self::Ordering, Self::Item>> {
+        self.iter().filter(|entry| entry.is_unordered())
+    }
+
+    /// Returns an iterator over owned handles to all collected implementations,
+    /// sorted by order.
+    ///
+    /// Like [concrete_arc](Store::concrete_arc), the handles aren't tied to
+    /// the lifetime of the store, so they can be moved into spawned tasks, etc.
+    fn iter_owned(&self) -> impl Iterator<Item = Arc<Self::Item>> + '_ {
+        self.iter().map(|entry| entry.0.instance().0.clone())
+    }
+
+    /// Returns owned handles to all collected implementations, keyed by
+    /// the [TypeId] of their concrete type, for building custom lookups.
+    ///
+    /// Like [iter_owned](Store::iter_owned), the handles aren't tied to
+    /// the lifetime of the store. The map doesn't preserve ordering.
+    fn to_type_map(&self) -> HashMap<TypeId, Arc<Self::Item>> {
+        self.iter()
+            .map(|entry| (entry.0.type_id(), entry.0.instance().0.clone()))
+            .collect()
+    }
+
+    /// Consumes the store into owned handles to all collected implementations,
+    /// sorted like [iter](Store::iter).
+    ///
+    /// # Example
+    /// ```ignore
+    /// for discover in store.into_ordered() {
+    ///     tokio::spawn(async move { discover.discover() });
+    /// }
+    /// ```
+    fn into_ordered(self) -> Vec<Arc<Self::Item>> {
+        self.iter_owned().collect()
+    }
+
     /// Returns an iterator over implementations with a specific ordering value.
     fn ordering<'a>(
         &'a self,
         ordering: &Self::Ordering,
     ) -> Option<impl Iterator<Item = EntryRef<'a, Self::Ordering, Self::Item>> + 'a>;
 
+    /// Returns an iterator over implementations with an ordering value at
+    /// or above `ordering`, sorted by order.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for handler in store.ordering_at_or_above(&Severity::High) {
+    ///     handler.alert(&incident);
+    /// }
+    /// ```
+    fn ordering_at_or_above<'a>(
+        &'a self,
+        ordering: &Self::Ordering,
+    ) -> impl Iterator<Item = EntryRef<'a, Self::Ordering, Self::Item>> + 'a;
+
+    /// Checks whether any implementation has a specific ordering value,
+    /// without building an iterator like [ordering](Store::ordering).
+    fn contains_ordering(&self, ordering: &Self::Ordering) -> bool;
+
+    /// Returns the lowest and highest ordering values in the store,
+    /// or [None] if it's empty.
+    fn ordering_bounds(&self) -> Option<(&Self::Ordering, &Self::Ordering)>;
+
+    /// Checks that no two implementations share an ordering, for stores
+    /// meant to have one implementation per slot.
+    ///
+    /// On failure, this returns the orderings of the first two neighbouring
+    /// implementations that aren't strictly increasing.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Err((ordering, _)) = store.assert_strictly_increasing() {
+    ///     panic!("more than one stage at {ordering:?}");
+    /// }
+    /// ```
+    fn assert_strictly_increasing(&self) -> Result<(), (Self::Ordering, Self::Ordering)> {
+        match self
+            .entries_slice()
+            .windows(2)
+            .find(|pair| pair[0].ordering() >= pair[1].ordering())
+        {
+            Some(pair) => Err((pair[0].ordering().clone(), pair[1].ordering().clone())),
+            None => Ok(()),
+        }
+    }
+
     /// Retrieves a reference to a specific concrete implementation by its type.
     ///
     /// This allows you to "downcast" or find a specific plugin if you know its
@@ -130,135 +387,1601 @@ pub trait Store: Sized {
     /// let linux_impl = store.concrete::<DiscoverLinux>();
     /// ```
     fn concrete<T: Any + Send + Sync>(&self) -> Option<ConcreteEntryRef<'_, T>>;
-}
 
-#[cfg(test)]
-mod store_tests {
-    use crate::{create_stain, stain, Store};
+    /// Retrieves a reference to a specific concrete implementation by its type,
+    /// reporting why it couldn't be retrieved on failure.
+    ///
+    /// This is [concrete](Store::concrete), but with a [ConcreteError]
+    /// instead of [None].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let linux_impl = store.try_concrete::<DiscoverLinux>()?;
+    /// ```
+    fn try_concrete<T: Any + Send + Sync>(&self) -> Result<ConcreteEntryRef<'_, T>, ConcreteError>;
 
-    trait Test {
-        fn test(&self) -> &'static str;
+    /// Retrieves an implementation by a [TypeId] known only at runtime,
+    /// without downcasting it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let entry = store.get_by_type_id(loaded_type_id);
+    /// ```
+    fn get_by_type_id(&self, type_id: TypeId) -> Option<EntryRef<'_, Self::Ordering, Self::Item>>;
+
+    /// Get an opaque, owned [PluginKey] for an implementation in this store,
+    /// which can be kept in other data structures and later resolved with
+    /// [get_by_key](Store::get_by_key).
+    ///
+    /// # Panics
+    /// If `entry` wasn't collected into this store.
+    fn key_of(&self, entry: &EntryRef<'_, Self::Ordering, Self::Item>) -> PluginKey<Self>;
+
+    /// Get a [PluginKey] for every registration of `T`, in the order
+    /// they were stained.
+    ///
+    /// Registrations dropped by the store's [DedupPolicy] are included;
+    /// only [get_registration](Store::get_registration) resolves them.
+    fn keys_of<T: Any>(&self) -> impl Iterator<Item = PluginKey<Self>> + '_;
+
+    /// Retrieves the implementation a [PluginKey] refers to, if that
+    /// registration is the one [iter](Store::iter) yields for its type.
+    ///
+    /// Keys of registrations dropped by the store's [DedupPolicy], or
+    /// whose type was removed from the store, resolve to [None].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let key = store.iter().find(|entry| entry.name() == "Json").map(|entry| store.key_of(&entry));
+    /// // ...
+    /// let json = key.and_then(|key| store.get_by_key(key));
+    /// ```
+    fn get_by_key(&self, key: PluginKey<Self>) -> Option<EntryRef<'_, Self::Ordering, Self::Item>>;
+
+    /// Retrieves the registration a [PluginKey] refers to, if its type is
+    /// still in the store, even if the store's [DedupPolicy] dropped it.
+    ///
+    /// *Note:* A dropped registration may have a different ordering or
+    /// metadata than the implementation [iter](Store::iter) yields.
+    fn get_registration(
+        &self,
+        key: PluginKey<Self>,
+    ) -> Option<EntryRef<'_, Self::Ordering, Self::Item>>;
+
+    /// Retrieves the implementation of a concrete type, checking that the
+    /// type was stained into the store exactly once.
+    ///
+    /// [concrete](Store::concrete) silently keeps one registration when a
+    /// type is stained more than once; this reports it as an error instead.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let settings = store.single::<Settings>()?;
+    /// ```
+    fn single<T: Any + Send + Sync>(&self) -> Result<ConcreteEntryRef<'_, T>, SingleError>;
+
+    /// Scans implementations in [iter](Store::iter) order, returning the
+    /// first result of `f` for one that downcasts to `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let version = store.find_map_concrete(|linux: &DiscoverLinux| linux.kernel_version());
+    /// ```
+    fn find_map_concrete<T, R, F>(&self, mut f: F) -> Option<R>
+    where
+        T: Any + Send + Sync,
+        F: FnMut(&T) -> Option<R>,
+    {
+        self.iter()
+            .find_map(|entry| entry.0.concrete::<T>().and_then(|concrete| f(&concrete)))
     }
 
-    create_stain! {
-        trait Test;
-        store: mod test;
+    /// Retrieves a clone of a specific concrete implementation by its type.
+    ///
+    /// The clone is detached from the store, so it can be mutated freely
+    /// without affecting the registered instance.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut linux_impl: DiscoverLinux = store.concrete_cloned::<DiscoverLinux>()?;
+    /// ```
+    fn concrete_cloned<T: Any + Send + Sync + Clone>(&self) -> Option<T> {
+        self.concrete::<T>().map(|concrete| T::clone(&concrete))
+    }
+
+    /// Retrieves the lowest-ordered implementation that downcasts to `T`.
+    ///
+    /// Only an implementation of type `T` downcasts to it, and a store keeps
+    /// one implementation per type (see [DedupPolicy]), so this is the same
+    /// lookup as [concrete](Store::concrete).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let linux_impl = store.first_concrete::<DiscoverLinux>();
+    /// ```
+    fn first_concrete<T: Any + Send + Sync>(&self) -> Option<ConcreteEntryRef<'_, T>> {
+        self.concrete::<T>()
+    }
+
+    /// Retrieves a specific concrete implementation by its type, along with
+    /// the trait object it was registered as.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let linux_impl = store.concrete_with_trait::<DiscoverLinux>().unwrap();
+    /// linux_impl.linux_only();
+    /// linux_impl.as_trait().discover();
+    /// ```
+    fn concrete_with_trait<T: Any + Send + Sync>(
+        &self,
+    ) -> Option<ConcreteTraitRef<'_, T, Self::Item>> {
+        self.get_by_type_id(TypeId::of::<T>())
+            .and_then(|entry| entry.0.concrete_with_trait::<T>())
+    }
+
+    /// Retrieves an owned handle to a specific concrete implementation by its type.
+    ///
+    /// Unlike [concrete](Store::concrete), the returned [Arc] isn't tied to
+    /// the lifetime of the store, so it can be moved into spawned tasks, etc.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let linux_impl: Arc<DiscoverLinux> = store.concrete_arc::<DiscoverLinux>()?;
+    /// ```
+    fn concrete_arc<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.concrete::<T>().map(|concrete| concrete.inner)
+    }
+
+    /// Retrieves an implementation by its registered [name](Entry::name).
+    ///
+    /// If multiple implementations share a name, the first one (by ordering)
+    /// is returned, or the last one under [NamePolicy::Last].
+    fn get_by_name(&self, name: &str) -> Option<EntryRef<'_, Self::Ordering, Self::Item>> {
+        let mut named = self.iter().filter(|entry| entry.name() == name);
+
+        match Self::NAME_POLICY {
+            NamePolicy::Last => named.last(),
+            NamePolicy::First | NamePolicy::Error => named.next(),
+        }
+    }
+
+    /// Returns an index of the implementations by their [name](Entry::name).
+    ///
+    /// Implementations sharing a name are resolved like
+    /// [get_by_name](Store::get_by_name).
+    fn name_index(&self) -> &HashMap<&'static str, EntryRef<'_, Self::Ordering, Self::Item>>;
+
+    /// Retrieves the trait object of an implementation by its registered
+    /// [name](Entry::name).
+    ///
+    /// This is [get_by_name](Store::get_by_name) without the [EntryRef]
+    /// wrapper, for code that only needs something like `&dyn Discover`.
+    fn get_dyn_by_name(&self, name: &str) -> Option<&Self::Item> {
+        self.get_by_name(name).map(|entry| entry.0.deref())
+    }
+
+    /// Retains only the implementations for which the predicate returns `true`.
+    ///
+    /// Like [Vec::retain], this prunes the collected store in place. Orderings
+    /// that end up with no implementations are removed entirely.
+    ///
+    /// # Example
+    /// ```ignore
+    /// store.retain(|entry| entry.name() != "DiscoverLinux");
+    /// ```
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&EntryRef<'_, Self::Ordering, Self::Item>) -> bool;
+
+    /// Splits the store into the implementations for which the predicate
+    /// returns `true`, and those for which it returns `false`.
+    ///
+    /// The predicate is called once per implementation.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let (cacheable, uncached) = store.partition(|entry| entry.is_cacheable());
+    /// ```
+    fn partition<F>(&self, mut f: F) -> (Self, Self)
+    where
+        Self: Clone,
+        F: FnMut(&EntryRef<'_, Self::Ordering, Self::Item>) -> bool,
+    {
+        let matching = self
+            .iter()
+            .filter(|entry| f(entry))
+            .map(|entry| entry.0.type_id())
+            .collect::<HashSet<_>>();
+
+        let (mut left, mut right) = (self.clone(), self.clone());
+        left.retain(|entry| matching.contains(&entry.0.type_id()));
+        right.retain(|entry| !matching.contains(&entry.0.type_id()));
+
+        (left, right)
+    }
+
+    /// Dispatches a [StoreVisitor] over all collected implementations,
+    /// sorted by order.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut report = Report::default();
+    /// store.accept(&mut report);
+    /// ```
+    fn accept<V>(&self, visitor: &mut V)
+    where
+        V: StoreVisitor<Self::Ordering, Self::Item>,
+    {
+        for entry in self.iter() {
+            visitor.visit(entry.name(), entry.ordering(), entry.0.deref());
+        }
+    }
+
+    /// Returns each ordering, rendered with [Display], along with the number
+    /// of implementations that have it.
+    ///
+    /// Only available when the ordering type implements [Display].
+    ///
+    /// # Example
+    /// ```ignore
+    /// for (ordering, count) in store.describe_ordering() {
+    ///     println!("priority {ordering}: {count} plugins");
+    /// }
+    /// ```
+    fn describe_ordering(&self) -> impl Iterator<Item = (String, usize)>
+    where
+        Self::Ordering: Display,
+    {
+        self.iter()
+            .map(|entry| entry.0.ordering())
+            .dedup_with_count()
+            .map(|(count, ordering)| (ordering.to_string(), count))
+    }
+
+    /// Folds the implementations in [iter](Store::iter) order, stopping
+    /// at the first one for which `f` returns an error.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let config = store.try_fold_ordered(Config::default(), |config, source| {
+    ///     source.load(config)
+    /// })?;
+    /// ```
+    fn try_fold_ordered<B, E, F>(&self, init: B, f: F) -> Result<B, E>
+    where
+        F: FnMut(B, EntryRef<'_, Self::Ordering, Self::Item>) -> Result<B, E>,
+    {
+        self.iter().try_fold(init, f)
+    }
+
+    /// Calls `f` on each implementation in [iter](Store::iter) order.
+    ///
+    /// For trait methods taking only `&self`, the `forward: ..;` option of
+    /// `create_stain! {...}` generates store methods that do this.
+    ///
+    /// # Example
+    /// ```ignore
+    /// store.call_all(|plugin| plugin.on_tick());
+    /// ```
+    fn call_all<F>(&self, mut f: F)
+    where
+        F: FnMut(&Self::Item),
+    {
+        self.iter().for_each(|entry| f(&entry));
+    }
+
+    /// Calls `f` on each implementation in [iter](Store::iter) order,
+    /// one at a time, stopping at the first error. Later implementations
+    /// aren't visited.
+    ///
+    /// # Example
+    /// ```ignore
+    /// store.for_each_try(|migration| migration.apply(&mut db))?;
+    /// ```
+    fn for_each_try<E, F>(&self, f: F) -> Result<(), E>
+    where
+        F: FnMut(EntryRef<'_, Self::Ordering, Self::Item>) -> Result<(), E>,
+    {
+        self.iter().try_for_each(f)
+    }
+
+    /// Checks that the store holds exactly the implementations in `expected`,
+    /// by [name](Entry::name), at the given orderings.
+    ///
+    /// # Example
+    /// ```ignore
+    /// store.verify_manifest(&[("DiscoverLinux", 0), ("DiscoverMac", 1)])?;
+    /// ```
+    fn verify_manifest(
+        &self,
+        expected: &[(&str, Self::Ordering)],
+    ) -> Result<(), ManifestError<Self::Ordering>> {
+        let mut error = ManifestError {
+            missing: Vec::new(),
+            extra: Vec::new(),
+            misordered: Vec::new(),
+        };
+
+        for (name, ordering) in expected {
+            match self.get_by_name(name) {
+                None => error.missing.push(name.to_string()),
+                Some(entry) if entry.ordering() != ordering => error.misordered.push((
+                    entry.name(),
+                    ordering.clone(),
+                    entry.ordering().clone(),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        error.extra = self
+            .iter()
+            .map(|entry| entry.name())
+            .filter(|name| !expected.iter().any(|(expected, _)| expected == name))
+            .collect();
+
+        if error.is_empty() {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Counts every registration collected into the store.
+    ///
+    /// *Note:* Registering a type more than once keeps a single entry for
+    /// it (see [DedupPolicy]), but each registration is counted here.
+    /// Use [count_unique_types](Store::count_unique_types) to count entries.
+    fn count_total(&self) -> usize;
+
+    /// Eagerly constructs every implementation, so construction panics
+    /// surface at startup rather than on first use.
+    ///
+    /// Construction panics are caught, and the first one is returned.
+    /// Those implementations stay uninitialized, and will panic again
+    /// if they're accessed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// plugins::Store::collect().init_all().expect("plugin failed to start");
+    /// ```
+    fn init_all(&self) -> Result<(), PluginPanic> {
+        let mut first_panic = None;
+        for entry in self.iter() {
+            if let Err(payload) = catch_unwind(AssertUnwindSafe(|| entry.0.init())) {
+                first_panic.get_or_insert(PluginPanic::from_payload(entry.name(), payload));
+            }
+        }
+
+        first_panic.map_or(Ok(()), Err)
+    }
+
+    /// Whether any implementation in the store has been constructed.
+    fn any_initialized(&self) -> bool {
+        self.iter().any(|entry| entry.0.is_initialized())
     }
 
-    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-    struct TestA;
+    /// Counts the distinct concrete types in the store.
+    fn count_unique_types(&self) -> usize {
+        self.iter().map(|entry| entry.0.type_id()).unique().count()
+    }
+
+    /// Counts the implementations registered under each ordering value.
+    ///
+    /// Orderings without implementations aren't included.
+    fn len_by_ordering(&self) -> BTreeMap<Self::Ordering, usize> {
+        self.iter()
+            .map(|entry| entry.0.ordering())
+            .dedup_with_count()
+            .map(|(count, ordering)| (ordering.clone(), count))
+            .collect()
+    }
+
+    /// Calls `f` on each implementation, in [iter](Store::iter) order,
+    /// catching panics so one misbehaving implementation doesn't stop
+    /// the rest from running.
+    ///
+    /// *Note:* Implementations are treated as unwind safe. If they hold
+    /// state behind interior mutability, a panic may leave it inconsistent
+    /// for later calls.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for result in store.dispatch(|plugin| plugin.run()) {
+    ///     if let Err(panic) = result {
+    ///         log::error!("{panic}");
+    ///     }
+    /// }
+    /// ```
+    fn dispatch<R, F>(&self, f: F) -> Vec<Result<R, PluginPanic>>
+    where
+        F: Fn(&Self::Item) -> R,
+    {
+        self.iter()
+            .map(|entry| {
+                catch_unwind(AssertUnwindSafe(|| f(&entry)))
+                    .map_err(|payload| PluginPanic::from_payload(entry.name(), payload))
+            })
+            .collect()
+    }
+
+    /// Sums a value computed from each implementation.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let total_weight = store.sum_by(|entry| entry.weight());
+    /// ```
+    fn sum_by<F>(&self, f: F) -> u64
+    where
+        F: FnMut(EntryRef<'_, Self::Ordering, Self::Item>) -> u64,
+    {
+        self.iter().map(f).sum()
+    }
+
+    /// Maps each implementation to a value, then folds the values
+    /// together with `reduce`, starting from `init`.
+    ///
+    /// Implementations are visited in [iter](Store::iter) order.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let heaviest = store.map_reduce(|entry| entry.weight(), u64::max, 0);
+    /// ```
+    fn map_reduce<A, M, R>(&self, map: M, reduce: R, init: A) -> A
+    where
+        M: FnMut(EntryRef<'_, Self::Ordering, Self::Item>) -> A,
+        R: FnMut(A, A) -> A,
+    {
+        self.iter().map(map).fold(init, reduce)
+    }
+}
+
+/// How a [Store] resolves implementations sharing a [name](Entry::name).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NamePolicy {
+    /// Name lookups return the first implementation, by ordering.
+    #[default]
+    First,
+    /// Name lookups return the last implementation, by ordering.
+    Last,
+    /// Collecting the store panics if names aren't unique.
+    Error,
+}
+
+/// How a [Store] resolves a type stained into it more than once.
+///
+/// Registrations are compared by the [Location] of their
+/// `stain! {...}` invocations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DedupPolicy {
+    /// The earliest registration is kept.
+    KeepFirst,
+    /// The latest registration is kept.
+    #[default]
+    KeepLast,
+    /// Collecting the store panics if a type is registered more than once.
+    Error,
+}
+
+/// A visitor over the implementations in a [Store].
+///
+/// See [accept](Store::accept).
+pub trait StoreVisitor<O, T: ?Sized> {
+    /// Visits a single implementation, given its [name](Entry::name),
+    /// [ordering](Entry::ordering), and trait object.
+    fn visit(&mut self, name: &str, ordering: &O, item: &T);
+}
+
+/// A fallible alternative to [Default], for implementations stained with
+/// `construct: try_default;` that validate invariants while constructing.
+///
+/// As implementations are constructed lazily, an error panics on first
+/// access. Use [init_all](Store::init_all) at startup to get it back as a
+/// [PluginPanic] naming the implementation.
+pub trait TryDefault: Sized {
+    /// Constructs the default instance, or reports why it can't be.
+    fn try_default() -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[cfg(test)]
+mod store_tests {
+    use crate::{create_stain, stain, Store};
+
+    trait Test {
+        fn test(&self) -> &'static str;
+    }
+
+    create_stain! {
+        trait Test;
+        store: mod test;
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct TestA;
+
+    impl Test for TestA {
+        fn test(&self) -> &'static str {
+            "TestA"
+        }
+    }
+
+    stain! {
+        store: test;
+        item: TestA;
+        ordering: 0;
+    }
+
+    #[derive(Default)]
+    struct TestB;
+
+    impl Test for TestB {
+        fn test(&self) -> &'static str {
+            "TestB"
+        }
+    }
+
+    stain! {
+        store: test;
+        item: TestB;
+        ordering: 1;
+    }
+
+    #[derive(Default)]
+    struct TestC;
+
+    impl Test for TestC {
+        fn test(&self) -> &'static str {
+            "TestC"
+        }
+    }
+
+    stain! {
+        store: test;
+        item: TestC;
+        ordering: 1;
+    }
+
+    #[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
+    enum Priority {
+        Low,
+        High,
+        Critical,
+    }
+
+    create_stain! {
+        trait Test;
+        ordering: Priority;
+        store: mod priority_test;
+    }
+
+    stain! {
+        store: priority_test;
+        item: TestA;
+        ordering: Priority::Low;
+    }
+
+    stain! {
+        store: priority_test;
+        item: TestB;
+        ordering: Priority::Critical;
+    }
+
+    stain! {
+        store: priority_test;
+        item: TestC;
+        ordering: Priority::High;
+    }
+
+    #[test]
+    fn concrete_downcast() {
+        let store = test::Store::collect();
+        let maybe_concrete = store.concrete::<TestA>();
+        let maybe_concrete = maybe_concrete.as_deref();
+
+        assert_eq!(maybe_concrete.copied(), Some(TestA));
+
+        let concrete = maybe_concrete.expect("TestA, by assertion.");
+        assert_eq!(concrete.test(), "TestA");
+    }
+
+    #[test]
+    fn concrete_as_trait() {
+        let store = test::Store::collect();
+        let concrete = store
+            .concrete_with_trait::<TestA>()
+            .expect("TestA is registered.");
+
+        assert_eq!(*concrete, TestA);
+        assert_eq!(concrete.as_trait().test(), "TestA");
+        assert_eq!(*concrete.into_concrete(), TestA);
+    }
+
+    #[test]
+    fn try_concrete_not_registered() {
+        use crate::ConcreteError;
+
+        struct TestD;
+
+        let store = test::Store::collect();
+
+        assert!(store.try_concrete::<TestA>().is_ok());
+        assert_eq!(
+            store.try_concrete::<TestD>().err(),
+            Some(ConcreteError::NotRegistered)
+        );
+    }
+
+    #[test]
+    fn first_concrete() {
+        struct TestD;
+
+        let store = test::Store::collect();
+
+        assert_eq!(store.first_concrete::<TestB>().unwrap().name(), "TestB");
+        assert!(store.first_concrete::<TestD>().is_none());
+    }
+
+    #[test]
+    fn iter_owned_outlives_store() {
+        let store = test::Store::collect();
+        let owned: Vec<_> = store.iter_owned().collect();
+        drop(store);
+
+        let names: Vec<_> = owned.iter().map(|item| item.test()).collect();
+        assert_eq!(names, ["TestA", "TestB", "TestC"]);
+    }
+
+    #[test]
+    fn find_map_concrete() {
+        let store = test::Store::collect();
+
+        assert_eq!(
+            store.find_map_concrete(|c: &TestC| Some(c.test())),
+            Some("TestC")
+        );
+        assert_eq!(store.find_map_concrete(|_: &TestA| None::<()>), None);
+    }
+
+    #[test]
+    fn into_ordered() {
+        let store = test::Store::collect();
+        let owned = store.into_ordered();
+
+        let names: Vec<_> = owned.iter().map(|item| item.test()).collect();
+        assert_eq!(names, ["TestA", "TestB", "TestC"]);
+    }
+
+    #[test]
+    fn concrete_arc_outlives_store() {
+        let store = test::Store::collect();
+        let concrete = store.concrete_arc::<TestA>();
+
+        drop(store);
+
+        let concrete = concrete.expect("TestA, registered.");
+        assert_eq!(*concrete, TestA);
+        assert_eq!(concrete.test(), "TestA");
+    }
+
+    #[test]
+    fn name_index() {
+        let store = test::Store::collect();
+        let index = store.name_index();
+
+        assert_eq!(index.len(), 3);
+        for name in ["TestA", "TestB", "TestC"] {
+            assert_eq!(index[name].test(), name);
+        }
+    }
+
+    #[test]
+    fn dyn_by_name() {
+        let store = test::Store::collect();
+
+        let test_b: Option<&dyn Test> = store.get_dyn_by_name("TestB").map(|test| test as _);
+        assert_eq!(test_b.map(|test| test.test()), Some("TestB"));
+
+        assert!(store.get_dyn_by_name("TestD").is_none());
+    }
+
+    #[test]
+    fn single_ordering() {
+        let store = test::Store::collect();
+        let ordering = store.ordering(&0);
+        assert!(ordering.is_some());
+
+        let mut ordering = ordering.expect("Ordering, by assertion.");
+        let maybe_test_a = ordering.next().map(|entry| entry.name());
+
+        assert_eq!(maybe_test_a, Some("TestA"));
+        assert!(ordering.next().is_none());
+    }
+
+    #[test]
+    fn strictly_increasing() {
+        let store = test::Store::collect();
+        assert_eq!(store.assert_strictly_increasing(), Err((1, 1)));
+
+        let store = test::Store::collect_filtered(|entry| entry.name() != "TestC");
+        assert_eq!(store.assert_strictly_increasing(), Ok(()));
+    }
+
+    #[test]
+    fn contains_ordering() {
+        let store = test::Store::collect();
+
+        assert!(store.contains_ordering(&1));
+        assert!(!store.contains_ordering(&2));
+    }
+
+    #[test]
+    fn multi_ordering() {
+        let store = test::Store::collect();
+        let ordering = store.ordering(&1);
+        assert!(ordering.is_some());
+
+        let mut ordering = ordering.expect("Ordering, by assertion.");
+
+        let maybe_test_b_or_c = ordering.next().map(|entry| entry.name());
+        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+
+        let maybe_test_b_or_c = ordering.next().map(|entry| entry.name());
+        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+
+        assert!(ordering.next().is_none());
+    }
+
+    #[test]
+    fn iter_all() {
+        let store = test::Store::collect();
+        let mut store_iter = store.iter();
+
+        let maybe_test_a = store_iter.next().map(|entry| entry.name());
+        assert_eq!(maybe_test_a, Some("TestA"));
+
+        let maybe_test_b_or_c = store_iter.next().map(|entry| entry.name());
+        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+
+        let maybe_test_b_or_c = store_iter.next().map(|entry| entry.name());
+        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+
+        assert!(store_iter.next().is_none());
+    }
+
+    #[test]
+    fn ordering_bounds() {
+        let store = priority_test::Store::collect();
+        assert_eq!(
+            store.ordering_bounds(),
+            Some((&Priority::Low, &Priority::Critical))
+        );
+
+        let mut store = test::Store::collect();
+        store.retain(|_| false);
+        assert_eq!(store.ordering_bounds(), None);
+    }
+
+    #[test]
+    fn entries_slice_search() {
+        let store = priority_test::Store::collect();
+        let entries = store.entries_slice();
+
+        let found = entries.binary_search_by_key(&&Priority::High, |entry| entry.ordering());
+        assert_eq!(found.map(|index| entries[index].name()), Ok("TestC"));
+        assert!(entries
+            .binary_search_by_key(&&Priority::Low, |entry| entry.ordering())
+            .is_ok());
+
+        let mut store = test::Store::collect();
+        store.retain(|entry| entry.name() != "TestA");
+        assert_eq!(store.entries_slice().len(), 2);
+    }
+
+    #[test]
+    fn at_or_above_priority() {
+        let store = priority_test::Store::collect();
+
+        let names: Vec<_> = store
+            .ordering_at_or_above(&Priority::High)
+            .map(|entry| entry.name())
+            .collect();
+        assert_eq!(names, ["TestC", "TestB"]);
+    }
+
+    #[test]
+    fn iter_desc_priority() {
+        let store = priority_test::Store::collect();
+        let orderings = store
+            .iter_desc()
+            .map(|entry| *entry.ordering())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            orderings,
+            [Priority::Critical, Priority::High, Priority::Low]
+        );
+    }
+
+    #[test]
+    fn registration_order() {
+        let store = priority_test::Store::collect();
+
+        let by_ordering = store.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+        assert_eq!(by_ordering, ["TestA", "TestC", "TestB"]);
+
+        let by_registration = store
+            .iter_registration_order()
+            .map(|entry| entry.name())
+            .collect::<Vec<_>>();
+        assert_eq!(by_registration, ["TestA", "TestB", "TestC"]);
+
+        let location = store.iter().next().map(|entry| entry.location());
+        assert_eq!(location.map(|location| location.file()), Some(file!()));
+    }
+
+    #[test]
+    fn iter_by_instance_key() {
+        // Reverse alphabetical, by what each implementation reports.
+        let store = test::Store::collect();
+        let names = store
+            .iter_by(|test| std::cmp::Reverse(test.test()))
+            .map(|entry| entry.name())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["TestC", "TestB", "TestA"]);
+    }
+
+    #[test]
+    fn stable_within_ordering() {
+        for _ in 0..8 {
+            let store = test::Store::collect();
+            let names = store.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+
+            assert_eq!(names, ["TestA", "TestB", "TestC"]);
+        }
+    }
+
+    #[test]
+    fn visitor_names() {
+        use crate::StoreVisitor;
+
+        #[derive(Default)]
+        struct Names(Vec<&'static str>);
+
+        impl StoreVisitor<u64, dyn Test + Send + Sync> for Names {
+            fn visit(&mut self, _name: &str, _ordering: &u64, item: &(dyn Test + Send + Sync)) {
+                self.0.push(item.test());
+            }
+        }
+
+        let store = test::Store::collect();
+        let mut names = Names::default();
+        store.accept(&mut names);
+
+        assert_eq!(names.0.len(), 3);
+        assert_eq!(names.0.first(), Some(&"TestA"));
+        assert!(names.0.contains(&"TestB"));
+        assert!(names.0.contains(&"TestC"));
+    }
+
+    #[test]
+    fn collect_filtered_below() {
+        let store = test::Store::collect_filtered(|entry| *entry.ordering() < 1);
+
+        let names: Vec<_> = store.iter().map(|entry| entry.name()).collect();
+        assert_eq!(names, ["TestA"]);
+        assert!(store.concrete::<TestB>().is_none());
+    }
+
+    #[test]
+    fn extend_with_filtered() {
+        let mut store = test::Store::collect_filtered(|entry| *entry.ordering() < 1);
+        let other = test::Store::collect_filtered(|entry| entry.name() != "TestB");
+        store.extend_with_store(&other);
+
+        let names: Vec<_> = store.iter().map(|entry| entry.name()).collect();
+        assert_eq!(names, ["TestA", "TestC"]);
+        assert_eq!(other.iter().count(), 2);
+        assert!(store.single::<TestA>().is_ok());
+    }
+
+    #[test]
+    fn key_resolves_entry() {
+        let store = test::Store::collect();
+        let key = store
+            .iter()
+            .find(|entry| entry.name() == "TestB")
+            .map(|entry| store.key_of(&entry))
+            .unwrap();
+
+        assert_eq!(store.get_by_key(key).unwrap().test(), "TestB");
+
+        let pruned = test::Store::collect_filtered(|entry| entry.name() != "TestB");
+        assert!(pruned.get_by_key(key).is_none());
+    }
+
+    #[test]
+    fn type_map_owned() {
+        use std::any::TypeId;
+
+        let map = test::Store::collect().to_type_map();
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&TypeId::of::<TestC>()].test(), "TestC");
+    }
+
+    #[test]
+    fn collect_into_existing() {
+        let mut store = test::Store::collect();
+        store.retain(|entry| entry.name() == "TestC");
+        assert_eq!(store.iter().count(), 1);
+
+        store.collect_into();
+
+        let fresh = test::Store::collect();
+        let names = store.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+        let fresh_names = fresh.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+
+        assert_eq!(names, fresh_names);
+        assert!(store.ordering(&0).is_some());
+        assert!(store.concrete::<TestB>().is_some());
+    }
+
+    #[test]
+    fn sum_and_map_reduce() {
+        let store = test::Store::collect();
+
+        assert_eq!(store.sum_by(|entry| entry.test().len() as u64), 15);
+        assert_eq!(
+            store.map_reduce(
+                |entry| entry.test().to_string(),
+                |a, b| a + &b,
+                String::new()
+            ),
+            "TestATestBTestC"
+        );
+    }
+
+    #[test]
+    fn try_fold_short_circuits() {
+        let store = test::Store::collect();
+        let mut visited = Vec::new();
+
+        let result = store.try_fold_ordered(0, |count, entry| {
+            visited.push(entry.test());
+            match entry.test() {
+                "TestB" => Err("TestB failed"),
+                _ => Ok(count + 1),
+            }
+        });
+
+        assert_eq!(result, Err("TestB failed"));
+        assert_eq!(visited, ["TestA", "TestB"]);
+    }
+
+    #[test]
+    fn for_each_try_stops_at_error() {
+        let store = test::Store::collect();
+        let mut visited = Vec::new();
+
+        let result = store.for_each_try(|entry| {
+            visited.push(entry.test());
+            match entry.test() {
+                "TestB" => Err("TestB failed"),
+                _ => Ok(()),
+            }
+        });
+
+        assert_eq!(result, Err("TestB failed"));
+        assert_eq!(visited, ["TestA", "TestB"]);
+    }
+
+    #[test]
+    fn verify_manifest() {
+        let store = test::Store::collect();
+
+        assert!(store
+            .verify_manifest(&[("TestA", 0), ("TestB", 1), ("TestC", 1)])
+            .is_ok());
+
+        let error = store
+            .verify_manifest(&[("TestA", 0), ("TestB", 2), ("TestD", 1)])
+            .unwrap_err();
+        assert_eq!(error.missing(), ["TestD"]);
+        assert_eq!(error.extra(), ["TestC"]);
+        assert_eq!(error.misordered(), [("TestB", 2, 1)]);
+    }
+
+    #[test]
+    fn len_by_ordering() {
+        let store = test::Store::collect();
+
+        assert_eq!(
+            store.len_by_ordering().into_iter().collect::<Vec<_>>(),
+            [(0, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn tiers_match_orderings() {
+        let store = test::Store::collect();
+        let tiers = store
+            .tiers()
+            .map(|(ordering, tier)| {
+                let names = tier.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+                (*ordering, names)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(tiers, [(0, vec!["TestA"]), (1, vec!["TestB", "TestC"])]);
+    }
+
+    #[test]
+    fn partition_by_parity() {
+        let store = test::Store::collect();
+        let (even, odd) = store.partition(|entry| entry.ordering() % 2 == 0);
+
+        let names =
+            |store: &test::Store| store.iter().map(|entry| entry.name()).collect::<Vec<_>>();
+        assert_eq!(names(&even), ["TestA"]);
+        assert_eq!(names(&odd), ["TestB", "TestC"]);
+        assert_eq!(store.iter().count(), 3);
+    }
+
+    #[test]
+    fn get_by_runtime_type_id() {
+        use std::any::TypeId;
+
+        let store = test::Store::collect();
+
+        let entry = store.get_by_type_id(TypeId::of::<TestB>()).unwrap();
+        assert_eq!(entry.test(), "TestB");
+        assert!(store.get_by_type_id(TypeId::of::<String>()).is_none());
+    }
+
+    #[test]
+    fn iter_named_pairs() {
+        let store = test::Store::collect();
+        let named = store
+            .iter_named()
+            .map(|(name, entry)| (name, entry.test()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            named,
+            [("TestA", "TestA"), ("TestB", "TestB"), ("TestC", "TestC")]
+        );
+    }
+
+    #[test]
+    fn describe_orderings() {
+        let store = test::Store::collect();
+        let described = store.describe_ordering().collect::<Vec<_>>();
+
+        assert_eq!(described, [("0".to_string(), 1), ("1".to_string(), 2)]);
+    }
+
+    #[test]
+    fn retain_even() {
+        let mut store = test::Store::collect();
+        store.retain(|entry| entry.ordering() % 2 == 0);
+
+        let mut store_iter = store.iter();
+
+        let maybe_test_a = store_iter.next().map(|entry| entry.name());
+        assert_eq!(maybe_test_a, Some("TestA"));
+        assert!(store_iter.next().is_none());
+
+        assert!(store.ordering(&1).is_none());
+        assert!(store.concrete::<TestB>().is_none());
+        assert!(store.concrete::<TestC>().is_none());
+    }
+}
+
+/***
+ * Generated Store
+ */
+
+#[doc(hidden)]
+/// *Internal API*
+///
+/// The storage behind the `Store` structs generated by `create_stain!`,
+/// which wrap it and forward to it.
+pub struct GeneratedStore<O, T>
+where
+    O: 'static,
+    T: ?Sized + 'static,
+{
+    entries: BTreeMap<O, Vec<&'static Entry<O, T>>>,
+    type_map: HashMap<TypeId, &'static Entry<O, T>>,
+    // How many times each type was stained, before deduplication.
+    registrations: HashMap<TypeId, usize>,
+    // Every registration, before deduplication, by its index in the collection.
+    registered: Vec<(usize, &'static Entry<O, T>)>,
+    ordered: Vec<EntryRef<'static, O, T>>,
+    names: HashMap<&'static str, EntryRef<'static, O, T>>,
+    name_policy: NamePolicy,
+}
+
+impl<O, T> GeneratedStore<O, T>
+where
+    O: Ord + Clone + 'static,
+    T: ?Sized + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            type_map: HashMap::new(),
+            registrations: HashMap::new(),
+            registered: Vec::new(),
+            ordered: Vec::new(),
+            names: HashMap::new(),
+            name_policy: NamePolicy::First,
+        }
+    }
+
+    /// Refills the store from `entries`, keeping one entry per type.
+    ///
+    /// Each entry is paired with its index in the collection it was
+    /// stained into, in increasing order.
+    pub fn collect_from(
+        &mut self,
+        entries: impl Iterator<Item = (usize, &'static Entry<O, T>)>,
+        name_policy: NamePolicy,
+        dedup_policy: DedupPolicy,
+    ) {
+        self.name_policy = name_policy;
+        self.type_map.clear();
+        self.registrations.clear();
+        self.registered.clear();
+        for (index, entry) in entries {
+            *self.registrations.entry(entry.type_id()).or_default() += 1;
+            self.registered.push((index, entry));
+
+            let kept = self.type_map.entry(entry.type_id()).or_insert(entry);
+            let replace = match dedup_policy {
+                DedupPolicy::KeepFirst => entry.location() < kept.location(),
+                DedupPolicy::KeepLast => entry.location() > kept.location(),
+                DedupPolicy::Error if !std::ptr::eq(*kept, entry) => {
+                    panic!("`{}` is stained more than once in the store", entry.name())
+                }
+                DedupPolicy::Error => false,
+            };
+
+            if replace {
+                *kept = entry;
+            }
+        }
+
+        // Buckets are cleared rather than dropped, so the ones
+        // that get refilled keep their allocations.
+        self.entries.values_mut().for_each(Vec::clear);
+
+        let sorted = self
+            .type_map
+            .values()
+            .cloned()
+            // Ties within an ordering are broken by tiebreak, then name,
+            // then type, so iteration doesn't depend on `HashMap` order.
+            .sorted_by(|a, b| {
+                a.cmp(b)
+                    .then_with(|| a.tiebreak().cmp(&b.tiebreak()))
+                    .then_with(|| a.name().cmp(b.name()))
+                    .then_with(|| a.type_id().cmp(&b.type_id()))
+            });
+
+        for entry in sorted {
+            self.entries
+                .entry(entry.ordering().clone())
+                .or_default()
+                .push(entry);
+        }
+
+        self.entries.retain(|_, entries| !entries.is_empty());
+        self.flatten();
+
+        if let NamePolicy::Error = name_policy {
+            if let Some(name) = self
+                .ordered
+                .iter()
+                .map(|entry| entry.name())
+                .duplicates()
+                .next()
+            {
+                panic!("`{name}` is registered more than once in the store");
+            }
+        }
+    }
+
+    /// Adds the entries of `other`, as if both had been collected together.
+    pub fn extend_from(&mut self, other: &Self, dedup_policy: DedupPolicy) {
+        // Both stores were collected from the same slice, so registrations
+        // they share have the same index.
+        let registered = self
+            .registered
+            .iter()
+            .merge_by(&other.registered, |(a, _), (b, _)| a <= b)
+            .dedup_by(|(a, _), (b, _)| a == b)
+            .copied()
+            .collect::<Vec<_>>();
+        self.collect_from(registered.into_iter(), self.name_policy, dedup_policy);
+    }
+
+    // Rebuilds the flat view of `entries` behind `entries_slice`,
+    // and the name index.
+    fn flatten(&mut self) {
+        self.ordered.clear();
+        self.ordered.extend(
+            self.entries
+                .values()
+                .flatten()
+                .map(|entry| EntryRef::from(*entry)),
+        );
+
+        self.names.clear();
+        for entry in &self.ordered {
+            match self.name_policy {
+                NamePolicy::Last => {
+                    self.names.insert(entry.name(), *entry);
+                }
+                NamePolicy::First | NamePolicy::Error => {
+                    self.names.entry(entry.name()).or_insert(*entry);
+                }
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = EntryRef<'_, O, T>> {
+        self.entries
+            .values()
+            .flat_map(|entries| entries.iter())
+            .map(|entry| EntryRef::from(*entry))
+    }
+
+    pub fn iter_desc(&self) -> impl Iterator<Item = EntryRef<'_, O, T>> {
+        self.entries
+            .values()
+            .rev()
+            .flat_map(|entries| entries.iter())
+            .map(|entry| EntryRef::from(*entry))
+    }
+
+    pub fn ordering<'a>(
+        &'a self,
+        ordering: &O,
+    ) -> Option<impl Iterator<Item = EntryRef<'a, O, T>> + 'a> {
+        let entries = self.entries.get(ordering)?;
+        Some(entries.iter().map(|entry| EntryRef::from(*entry)))
+    }
+
+    pub fn ordering_at_or_above<'a>(
+        &'a self,
+        ordering: &O,
+    ) -> impl Iterator<Item = EntryRef<'a, O, T>> + 'a {
+        self.entries
+            .range((Bound::Included(ordering), Bound::Unbounded))
+            .flat_map(|(_, entries)| entries.iter())
+            .map(|entry| EntryRef::from(*entry))
+    }
+
+    pub fn entries_slice(&self) -> &[EntryRef<'_, O, T>] {
+        &self.ordered
+    }
+
+    pub fn name_index(&self) -> &HashMap<&'static str, EntryRef<'_, O, T>> {
+        &self.names
+    }
+
+    pub fn contains_ordering(&self, ordering: &O) -> bool {
+        self.entries.contains_key(ordering)
+    }
+
+    pub fn ordering_bounds(&self) -> Option<(&O, &O)> {
+        let (first, _) = self.entries.first_key_value()?;
+        let (last, _) = self.entries.last_key_value()?;
+
+        Some((first, last))
+    }
+
+    pub fn concrete<C: Any + Send + Sync>(&self) -> Option<ConcreteEntryRef<'_, C>> {
+        self.type_map.get(&TypeId::of::<C>())?.concrete::<C>()
+    }
+
+    pub fn try_concrete<C: Any + Send + Sync>(
+        &self,
+    ) -> Result<ConcreteEntryRef<'_, C>, ConcreteError> {
+        let entry = self
+            .type_map
+            .get(&TypeId::of::<C>())
+            .ok_or(ConcreteError::NotRegistered)?;
+
+        entry
+            .concrete::<C>()
+            .ok_or_else(|| ConcreteError::DowncastFailed {
+                expected: std::any::type_name::<C>(),
+                found: entry.name(),
+            })
+    }
+
+    pub fn single<C: Any + Send + Sync>(&self) -> Result<ConcreteEntryRef<'_, C>, SingleError> {
+        match self.registrations.get(&TypeId::of::<C>()) {
+            Some(1) => self.concrete::<C>().ok_or(SingleError::Missing),
+            Some(&count) => Err(SingleError::Multiple(count)),
+            None => Err(SingleError::Missing),
+        }
+    }
+
+    pub fn count_total(&self) -> usize {
+        self.registrations.values().sum()
+    }
+
+    pub fn get_by_type_id(&self, type_id: TypeId) -> Option<EntryRef<'_, O, T>> {
+        self.type_map
+            .get(&type_id)
+            .map(|entry| EntryRef::from(*entry))
+    }
+
+    pub fn key_of<S>(&self, entry: &EntryRef<'_, O, T>) -> PluginKey<S> {
+        let (index, _) = self
+            .registered
+            .iter()
+            .find(|(_, registered)| std::ptr::eq(*registered, entry.0))
+            .unwrap_or_else(|| panic!("`{}` isn't collected into the store", entry.name()));
+
+        PluginKey::new(entry.0.type_id(), *index)
+    }
+
+    pub fn keys_of<S, C: Any>(&self) -> impl Iterator<Item = PluginKey<S>> + '_ {
+        self.registered
+            .iter()
+            .filter(|(_, entry)| Entry::type_id(entry) == TypeId::of::<C>())
+            .map(|(index, entry)| PluginKey::new(Entry::type_id(entry), *index))
+    }
+
+    pub fn get_by_key<S>(&self, key: PluginKey<S>) -> Option<EntryRef<'_, O, T>> {
+        let kept = self.type_map.get(&key.type_id)?;
+
+        self.get_registration(key)
+            .filter(|entry| std::ptr::eq(entry.0, *kept))
+    }
+
+    pub fn get_registration<S>(&self, key: PluginKey<S>) -> Option<EntryRef<'_, O, T>> {
+        let position = self
+            .registered
+            .binary_search_by_key(&key.index, |(index, _)| *index)
+            .ok()?;
+
+        Some(self.registered[position].1)
+            .filter(|entry| Entry::type_id(entry) == key.type_id)
+            .map(EntryRef::from)
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&EntryRef<'_, O, T>) -> bool,
+    {
+        let mut removed = Vec::new();
+
+        self.entries.retain(|_, entries| {
+            entries.retain(|entry| {
+                let keep = f(&EntryRef::from(*entry));
+                if !keep {
+                    removed.push(Entry::type_id(entry));
+                }
+
+                keep
+            });
+
+            // Drop buckets that were emptied out, so they don't
+            // show up as (empty) orderings.
+            !entries.is_empty()
+        });
 
-    impl Test for TestA {
-        fn test(&self) -> &'static str {
-            "TestA"
+        for type_id in removed {
+            self.type_map.remove(&type_id);
+            self.registrations.remove(&type_id);
         }
-    }
+        self.registered
+            .retain(|(_, entry)| self.type_map.contains_key(&Entry::type_id(entry)));
 
-    stain! {
-        store: test;
-        item: TestA;
-        ordering: 0;
+        self.flatten();
     }
+}
 
-    #[derive(Default)]
-    struct TestB;
+impl<O, T> Default for GeneratedStore<O, T>
+where
+    O: Ord + Clone + 'static,
+    T: ?Sized + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    impl Test for TestB {
-        fn test(&self) -> &'static str {
-            "TestB"
+impl<O, T> Clone for GeneratedStore<O, T>
+where
+    O: Clone + 'static,
+    T: ?Sized + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            type_map: self.type_map.clone(),
+            registrations: self.registrations.clone(),
+            registered: self.registered.clone(),
+            ordered: self.ordered.clone(),
+            names: self.names.clone(),
+            name_policy: self.name_policy,
         }
     }
+}
 
-    stain! {
-        store: test;
-        item: TestB;
-        ordering: 1;
-    }
+/***
+ * Errors
+ */
 
-    #[derive(Default)]
-    struct TestC;
+/// The reason [try_concrete](Store::try_concrete) couldn't retrieve
+/// an implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConcreteError {
+    /// No implementation of the requested type is in the store.
+    NotRegistered,
+    /// An implementation is registered under the requested type's [TypeId],
+    /// but couldn't be downcast to it.
+    DowncastFailed {
+        /// The name of the requested type.
+        expected: &'static str,
+        /// The [name](Entry::name) of the registered implementation.
+        found: &'static str,
+    },
+}
 
-    impl Test for TestC {
-        fn test(&self) -> &'static str {
-            "TestC"
+impl Display for ConcreteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotRegistered => write!(f, "implementation isn't registered in the store"),
+            Self::DowncastFailed { expected, found } => {
+                write!(f, "couldn't downcast `{found}` to `{expected}`")
+            }
         }
     }
+}
 
-    stain! {
-        store: test;
-        item: TestC;
-        ordering: 1;
-    }
-
-    #[test]
-    fn concrete_downcast() {
-        let store = test::Store::collect();
-        let maybe_concrete = store.concrete::<TestA>();
-        let maybe_concrete = maybe_concrete.as_deref();
+impl std::error::Error for ConcreteError {}
 
-        assert_eq!(maybe_concrete.copied(), Some(TestA));
+/// The reason [single](Store::single) couldn't retrieve an implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SingleError {
+    /// No implementation of the requested type is in the store.
+    Missing,
+    /// The requested type was stained into the store this many times.
+    Multiple(usize),
+}
 
-        let concrete = maybe_concrete.expect("TestA, by assertion.");
-        assert_eq!(concrete.test(), "TestA");
+impl Display for SingleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "implementation isn't registered in the store"),
+            Self::Multiple(count) => {
+                write!(
+                    f,
+                    "implementation is registered {count} times, expected once"
+                )
+            }
+        }
     }
+}
 
-    #[test]
-    fn single_ordering() {
-        let store = test::Store::collect();
-        let ordering = store.ordering(&0);
-        assert!(ordering.is_some());
+impl std::error::Error for SingleError {}
 
-        let mut ordering = ordering.expect("Ordering, by assertion.");
-        let maybe_test_a = ordering.next().map(|entry| entry.name());
+/// A panic caught while [dispatching](Store::dispatch) to an implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginPanic {
+    name: &'static str,
+    message: Option<String>,
+}
 
-        assert_eq!(maybe_test_a, Some("TestA"));
-        assert!(ordering.next().is_none());
+impl PluginPanic {
+    fn from_payload(name: &'static str, payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            name,
+            message: payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned()),
+        }
     }
 
-    #[test]
-    fn multi_ordering() {
-        let store = test::Store::collect();
-        let ordering = store.ordering(&1);
-        assert!(ordering.is_some());
+    /// Get the [name](Entry::name) of the implementation that panicked.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
 
-        let mut ordering = ordering.expect("Ordering, by assertion.");
+    /// Get the panic message, if the payload was a string.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
 
-        let maybe_test_b_or_c = ordering.next().map(|entry| entry.name());
-        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+impl Display for PluginPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "`{}` panicked: {message}", self.name),
+            None => write!(f, "`{}` panicked", self.name),
+        }
+    }
+}
 
-        let maybe_test_b_or_c = ordering.next().map(|entry| entry.name());
-        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+impl std::error::Error for PluginPanic {}
 
-        assert!(ordering.next().is_none());
+/// The differences found by [verify_manifest](Store::verify_manifest).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestError<O> {
+    missing: Vec<String>,
+    extra: Vec<&'static str>,
+    misordered: Vec<(&'static str, O, O)>,
+}
+
+impl<O> ManifestError<O> {
+    /// Get the names in the manifest that aren't registered.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
     }
 
-    #[test]
-    fn iter_all() {
-        let store = test::Store::collect();
-        let mut store_iter = store.iter();
+    /// Get the names registered, but not in the manifest.
+    pub fn extra(&self) -> &[&'static str] {
+        &self.extra
+    }
 
-        let maybe_test_a = store_iter.next().map(|entry| entry.name());
-        assert_eq!(maybe_test_a, Some("TestA"));
+    /// Get the implementations registered with a different ordering than
+    /// in the manifest, as `(name, expected, found)`.
+    pub fn misordered(&self) -> &[(&'static str, O, O)] {
+        &self.misordered
+    }
 
-        let maybe_test_b_or_c = store_iter.next().map(|entry| entry.name());
-        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+    fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.misordered.is_empty()
+    }
+}
 
-        let maybe_test_b_or_c = store_iter.next().map(|entry| entry.name());
-        assert!(maybe_test_b_or_c == Some("TestB") || maybe_test_b_or_c == Some("TestC"));
+impl<O: std::fmt::Debug> Display for ManifestError<O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "store doesn't match manifest:")?;
+        for name in &self.missing {
+            write!(f, " missing `{name}`;")?;
+        }
+        for name in &self.extra {
+            write!(f, " unexpected `{name}`;")?;
+        }
+        for (name, expected, found) in &self.misordered {
+            write!(f, " `{name}` expected at {expected:?}, found at {found:?};")?;
+        }
 
-        assert!(store_iter.next().is_none());
+        Ok(())
     }
 }
 
+impl<O: std::fmt::Debug> std::error::Error for ManifestError<O> {}
+
 /***
  * Entry
  */
 
+/// An ordering and the implementations registered under it, as
+/// returned by [tiers](Store::tiers).
+pub type Tier<'e, O, T> = (&'e O, &'e [EntryRef<'e, O, T>]);
+
 #[rustversion::since(1.91)]
 pub struct Entry<O, T: ?Sized> {
     type_id: TypeId,
     ordering: O,
     name: &'static str,
+    name_fn: Option<fn() -> &'static str>,
+    location: Location,
+    meta: &'static [(&'static str, &'static str)],
+    tiebreak: u32,
+    unordered: bool,
+    initialized: AtomicBool,
     inner: LazyLock<(Arc<T>, Arc<dyn Any + Send + Sync>)>,
 }
 
@@ -267,6 +1990,12 @@ pub struct Entry<O, T: ?Sized> {
     type_id: LazyLock<TypeId>,
     ordering: O,
     name: &'static str,
+    name_fn: Option<fn() -> &'static str>,
+    location: Location,
+    meta: &'static [(&'static str, &'static str)],
+    tiebreak: u32,
+    unordered: bool,
+    initialized: AtomicBool,
     inner: LazyLock<(Arc<T>, Arc<dyn Any + Send + Sync>)>,
 }
 
@@ -275,12 +2004,20 @@ where
     T: ?Sized,
 {
     /// Get the [TypeId] of the underlying concrete type.
+    ///
+    /// A type can be stained more than once, so this doesn't identify a
+    /// single registration; use a [PluginKey] (see [key_of](Store::key_of))
+    /// as a key for per-implementation data.
     #[rustversion::before(1.91)]
     pub fn type_id(&self) -> TypeId {
         *self.type_id
     }
 
     /// Get the [TypeId] of the underlying concrete type.
+    ///
+    /// A type can be stained more than once, so this doesn't identify a
+    /// single registration; use a [PluginKey] (see [key_of](Store::key_of))
+    /// as a key for per-implementation data.
     #[rustversion::since(1.91)]
     pub fn type_id(&self) -> TypeId {
         self.type_id
@@ -301,11 +2038,68 @@ where
     /// Get the name of the registered implementation.
     ///
     /// *Note:* This is just the `stringify!(..)`d name of the
-    /// implementation that was passed into the `stain! {...}` macro.
+    /// implementation that was passed into the `stain! {...}` macro,
+    /// unless it was given a `name: "..";`, or `name: type_name;` for
+    /// [std::any::type_name] (which reflects generic arguments, but
+    /// isn't guaranteed to be stable across compiler versions).
     /// The main use of this is for logging/debugging. The use of this
     /// field for application logic is discouraged.
     pub fn name(&self) -> &'static str {
-        self.name
+        match self.name_fn {
+            Some(name_fn) => name_fn(),
+            None => self.name,
+        }
+    }
+
+    /// Get the [Location] of the `stain! {...}` invocation that
+    /// registered this implementation.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Get the secondary key ordering this implementation among those
+    /// sharing its ordering, set by the `tiebreak: ..;` clause of
+    /// `stain! {...}`. Defaults to `0`.
+    pub fn tiebreak(&self) -> u32 {
+        self.tiebreak
+    }
+
+    /// Whether this implementation was registered with `unordered: true;`,
+    /// i.e. it has no meaningful order relative to other unordered
+    /// implementations, and may be run concurrently with them.
+    pub fn is_unordered(&self) -> bool {
+        self.unordered
+    }
+
+    /// Get the metadata attached by the `meta: { .. };` clause of
+    /// `stain! {...}`, as key/value pairs in declaration order.
+    ///
+    /// Keys are unique. This is empty when no metadata was given.
+    pub fn meta(&self) -> &'static [(&'static str, &'static str)] {
+        self.meta
+    }
+
+    /// Whether the underlying instance has been constructed yet.
+    ///
+    /// Implementations are constructed lazily, the first time they're
+    /// accessed. See [init](Entry::init) to construct one eagerly.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.load(atomic::Ordering::Acquire)
+    }
+
+    /// Constructs the underlying instance, if it hasn't been already.
+    pub fn init(&self) {
+        self.instance();
+    }
+
+    fn instance(&self) -> &(Arc<T>, Arc<dyn Any + Send + Sync>) {
+        let instance = LazyLock::force(&self.inner);
+        // Only written once, as this is on the path of every call
+        // into the implementation.
+        if !self.initialized.load(atomic::Ordering::Relaxed) {
+            self.initialized.store(true, atomic::Ordering::Release);
+        }
+        instance
     }
 
     /// Attempts to downcast the Entry to its underlying type.
@@ -320,16 +2114,16 @@ where
     where
         C: Any + Send + Sync,
     {
-        self.inner
+        self.instance()
             .1
             .clone()
             .downcast::<C>()
             .ok()
             .map(|concrete| ConcreteEntryRef {
                 type_id: *self.type_id,
-                name: self.name,
+                name: self.name(),
                 inner: concrete,
-                _phantom: Default::default(),
+                _phantom: PhantomData,
             })
     }
 
@@ -345,19 +2139,31 @@ where
     where
         C: Any + Send + Sync,
     {
-        self.inner
+        self.instance()
             .1
             .clone()
             .downcast::<C>()
             .ok()
             .map(|concrete| ConcreteEntryRef {
                 type_id: self.type_id,
-                name: self.name,
+                name: self.name(),
                 inner: concrete,
-                _phantom: Default::default(),
+                _phantom: PhantomData,
             })
     }
 
+    /// Attempts to downcast the Entry to its underlying type, like
+    /// [concrete](Entry::concrete), keeping the trait object alongside.
+    pub fn concrete_with_trait<C>(&self) -> Option<ConcreteTraitRef<'_, C, T>>
+    where
+        C: Any + Send + Sync,
+    {
+        self.concrete::<C>().map(|concrete| ConcreteTraitRef {
+            concrete,
+            item: self.instance().0.deref(),
+        })
+    }
+
     #[doc(hidden)]
     #[rustversion::before(1.91)]
     /// *Internal API*
@@ -375,6 +2181,12 @@ where
             type_id: LazyLock::new(type_id),
             ordering,
             name,
+            name_fn: None,
+            location: Location::UNKNOWN,
+            meta: &[],
+            tiebreak: 0,
+            unordered: false,
+            initialized: AtomicBool::new(false),
         }
     }
 
@@ -394,11 +2206,120 @@ where
             inner: LazyLock::new(init),
             ordering,
             name,
+            name_fn: None,
+            location: Location::UNKNOWN,
+            meta: &[],
+            tiebreak: 0,
+            unordered: false,
+            initialized: AtomicBool::new(false),
             type_id,
         }
     }
+
+    #[doc(hidden)]
+    /// *Internal API*
+    pub const fn with_location(mut self, file: &'static str, line: u32, column: u32) -> Self {
+        self.location = Location { file, line, column };
+        self
+    }
+
+    #[doc(hidden)]
+    /// *Internal API*
+    pub const fn with_meta(mut self, meta: &'static [(&'static str, &'static str)]) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    #[doc(hidden)]
+    /// *Internal API*
+    pub const fn with_name_fn(mut self, name_fn: Option<fn() -> &'static str>) -> Self {
+        self.name_fn = name_fn;
+        self
+    }
+
+    #[doc(hidden)]
+    /// *Internal API*
+    pub const fn with_unordered(mut self, unordered: bool) -> Self {
+        self.unordered = unordered;
+        self
+    }
+
+    #[doc(hidden)]
+    /// *Internal API*
+    pub const fn with_tiebreak(mut self, tiebreak: u32) -> Self {
+        self.tiebreak = tiebreak;
+        self
+    }
+}
+
+#[doc(hidden)]
+/// *Internal API*
+///
+/// Checks for repeated keys in `meta: { .. };` at compile time.
+pub const fn has_duplicate_meta_keys(meta: &[(&str, &str)]) -> bool {
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+
+        true
+    }
+
+    let mut i = 0;
+    while i < meta.len() {
+        let mut j = i + 1;
+        while j < meta.len() {
+            if str_eq(meta[i].0, meta[j].0) {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+
+    false
 }
 
+#[doc(hidden)]
+/// *Internal API*
+///
+/// Not implemented by anything, so [check_name] reports a `name:` that
+/// isn't a string literal or `type_name` at the offending token.
+#[diagnostic::on_unimplemented(
+    message = "expected a string literal or `type_name` for `name:`, found `{Self}`",
+    label = "not a string literal or `type_name`"
+)]
+pub trait ValidName {}
+
+#[doc(hidden)]
+/// *Internal API*
+pub const fn check_name<N: ValidName>() {}
+
+#[doc(hidden)]
+/// *Internal API*
+///
+/// Not implemented by anything, so [check_create_stain_option] reports an
+/// unknown `create_stain!` option at the offending token.
+#[diagnostic::on_unimplemented(
+    message = "unknown `create_stain!` option `{Self}`",
+    label = "expected one of `trait`, `ordering`, `type`, `const`, `bounds`, \
+             `name_policy`, `dedup_policy`, `forward`, `prefix` or `store`"
+)]
+pub trait CreateStainOption {}
+
+#[doc(hidden)]
+/// *Internal API*
+pub const fn check_create_stain_option<O: CreateStainOption>() {}
+
 impl<O, T> Deref for Entry<O, T>
 where
     T: ?Sized,
@@ -406,7 +2327,7 @@ where
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.inner.0.deref()
+        self.instance().0.deref()
     }
 }
 
@@ -469,7 +2390,7 @@ mod entry_tests {
         use std::any::{Any, TypeId};
 
         // Out of order entries, by ordering.
-        let mut entries = vec![
+        let mut entries = [
             Entry::new(TypeId::of::<TestC>(), 3u64, "TestC", || {
                 let instance = TestC;
                 let shared = Arc::new(instance);
@@ -502,7 +2423,7 @@ mod entry_tests {
         // Should sort by ordering...
         entries.sort();
 
-        assert_eq!(entries.get(0).map(|inner| inner.name()), Some("TestA"));
+        assert_eq!(entries.first().map(|inner| inner.name()), Some("TestA"));
         assert_eq!(entries.get(1).map(|inner| inner.name()), Some("TestB"));
         assert_eq!(entries.get(2).map(|inner| inner.name()), Some("TestC"));
     }
@@ -514,7 +2435,7 @@ mod entry_tests {
         use std::any::{Any, TypeId};
 
         // Out of order entries, by ordering.
-        let mut entries = vec![
+        let mut entries = [
             Entry::new(
                 || TypeId::of::<TestC>(),
                 3u64,
@@ -562,7 +2483,7 @@ mod entry_tests {
         // Should sort by ordering...
         entries.sort();
 
-        assert_eq!(entries.get(0).map(|inner| inner.name()), Some("TestA"));
+        assert_eq!(entries.first().map(|inner| inner.name()), Some("TestA"));
         assert_eq!(entries.get(1).map(|inner| inner.name()), Some("TestB"));
         assert_eq!(entries.get(2).map(|inner| inner.name()), Some("TestC"));
     }
@@ -581,7 +2502,7 @@ mod entry_tests {
         }
 
         // Out of order entries, by ordering.
-        let mut entries = vec![
+        let mut entries = [
             Entry::new(TypeId::of::<TestC>(), Priority::High, "TestC", || {
                 let instance = TestC;
                 let shared = Arc::new(instance);
@@ -614,7 +2535,7 @@ mod entry_tests {
         // Should sort by ordering...
         entries.sort();
 
-        assert_eq!(entries.get(0).map(|inner| inner.name()), Some("TestA"));
+        assert_eq!(entries.first().map(|inner| inner.name()), Some("TestA"));
         assert_eq!(entries.get(1).map(|inner| inner.name()), Some("TestB"));
         assert_eq!(entries.get(2).map(|inner| inner.name()), Some("TestC"));
     }
@@ -633,7 +2554,7 @@ mod entry_tests {
         }
 
         // Out of order entries, by ordering.
-        let mut entries = vec![
+        let mut entries = [
             Entry::new(
                 || TypeId::of::<TestC>(),
                 Priority::High,
@@ -681,7 +2602,7 @@ mod entry_tests {
         // Should sort by ordering...
         entries.sort();
 
-        assert_eq!(entries.get(0).map(|inner| inner.name()), Some("TestA"));
+        assert_eq!(entries.first().map(|inner| inner.name()), Some("TestA"));
         assert_eq!(entries.get(1).map(|inner| inner.name()), Some("TestB"));
         assert_eq!(entries.get(2).map(|inner| inner.name()), Some("TestC"));
     }
@@ -734,6 +2655,50 @@ mod entry_tests {
     }
 }
 
+/***
+ * Location
+ */
+
+/// The source location of a `stain! {...}` invocation.
+///
+/// Locations order by file path, then line, then column, i.e. in
+/// definition order within a file, and alphabetically across files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    file: &'static str,
+    line: u32,
+    column: u32,
+}
+
+impl Location {
+    const UNKNOWN: Self = Self {
+        file: "",
+        line: 0,
+        column: 0,
+    };
+
+    /// The file containing the invocation, as given by [file!].
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of the invocation, as given by [line!].
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The column of the invocation, as given by [column!].
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
 /***
  * Concrete Entry
  */
@@ -763,10 +2728,49 @@ impl<'e, C> Deref for ConcreteEntryRef<'e, C> {
     }
 }
 
+/// A [ConcreteEntryRef] which keeps the trait object it was downcast from.
+pub struct ConcreteTraitRef<'e, C, T: ?Sized> {
+    concrete: ConcreteEntryRef<'e, C>,
+    item: &'e T,
+}
+
+impl<'e, C, T: ?Sized> ConcreteTraitRef<'e, C, T> {
+    pub fn type_id(&self) -> TypeId {
+        self.concrete.type_id()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.concrete.name()
+    }
+
+    /// Get the trait object this was downcast from, to call trait
+    /// methods without looking the entry up again.
+    pub fn as_trait(&self) -> &'e T {
+        self.item
+    }
+
+    /// Drop the trait object, keeping the concrete view.
+    pub fn into_concrete(self) -> ConcreteEntryRef<'e, C> {
+        self.concrete
+    }
+}
+
+impl<'e, C, T: ?Sized> Deref for ConcreteTraitRef<'e, C, T> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        self.concrete.deref()
+    }
+}
+
 /***
  * Entry Ref
  */
 
+/// A reference to an [Entry] in a store.
+///
+/// *Note:* This doesn't implement [Eq] or [Hash]; use a [PluginKey]
+/// (see [key_of](Store::key_of)) to key data by implementation.
 pub struct EntryRef<'e, O, T>(&'e Entry<O, T>)
 where
     T: ?Sized;
@@ -778,10 +2782,21 @@ where
     type Target = Entry<O, T>;
 
     fn deref(&self) -> &'e Self::Target {
-        &self.0
+        self.0
+    }
+}
+
+impl<'e, O, T> Clone for EntryRef<'e, O, T>
+where
+    T: ?Sized,
+{
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
+impl<'e, O, T> Copy for EntryRef<'e, O, T> where T: ?Sized {}
+
 impl<'e, O, T> From<&'e Entry<O, T>> for EntryRef<'e, O, T>
 where
     T: ?Sized,
@@ -790,3 +2805,65 @@ where
         Self(value)
     }
 }
+
+/***
+ * Plugin Key
+ */
+
+/// An owned reference to a registration of an implementation,
+/// in a store of type `S`.
+///
+/// See [key_of](Store::key_of).
+pub struct PluginKey<S> {
+    type_id: TypeId,
+    // The registration's index in the store's collection.
+    index: usize,
+    _store: PhantomData<fn() -> S>,
+}
+
+impl<S> PluginKey<S> {
+    fn new(type_id: TypeId, index: usize) -> Self {
+        Self {
+            type_id,
+            index,
+            _store: PhantomData,
+        }
+    }
+
+    /// Get the [TypeId] of the implementation this key refers to.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+}
+
+impl<S> Clone for PluginKey<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for PluginKey<S> {}
+
+impl<S> PartialEq for PluginKey<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && self.index == other.index
+    }
+}
+
+impl<S> Eq for PluginKey<S> {}
+
+impl<S> Hash for PluginKey<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_id.hash(state);
+        self.index.hash(state);
+    }
+}
+
+impl<S> std::fmt::Debug for PluginKey<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PluginKey")
+            .field(&self.type_id)
+            .field(&self.index)
+            .finish()
+    }
+}
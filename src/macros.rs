@@ -69,6 +69,10 @@
 /// }
 /// ```
 ///
+/// The store module also exports the ordering type as `Ordering`, so
+/// registrations elsewhere can write `job_store::Ordering::Critical`
+/// without importing `Priority`.
+///
 /// ## 3. Generics and Associated Types (GATs)
 ///
 /// `stain` supports generics on the trait and Generic Associated Types.
@@ -109,6 +113,11 @@
 /// }
 /// ```
 ///
+/// Const generics are given after the type generics, with `const <value>;`,
+/// e.g. `const 16;` for `trait Buffer<const N: usize>`. As they're passed
+/// to the trait in that order, the trait must declare its type parameters
+/// before its const parameters.
+///
 /// ## 4. Prefixes
 ///
 /// If you have multiple stain stores in your binary, `linkme` might collision
@@ -130,6 +139,13 @@
 /// }
 /// ```
 ///
+/// The linker section is named after the generated static,
+/// `__STAIN_{PREFIX}_{STORE}`, so the prefix has to be given
+/// on each `create_stain!` invocation. There's no crate-wide default:
+/// a declarative macro can't read crate attributes or environment
+/// variables into an identifier. Using the crate name as the prefix
+/// is a good convention for stores in shared/workspace crates.
+///
 /// ## 5. Visibility
 ///
 /// Visibility can be specified by adding a visibility to the store declaration.
@@ -138,44 +154,353 @@
 ///
 /// All this does is add a visibility to the generated module and makes sure
 /// that all the generated items in the module have the correct visibility, too.
+///
+/// Doc comments and other attributes on the store declaration are put on
+/// the generated module, e.g. to describe the registry on docs.rs:
+///
+/// ```ignore
+/// create_stain! {
+///     trait Discover;
+///     /// Discovery strategies, tried in order.
+///     store: pub mod discover_store;
+/// }
+/// ```
+///
+/// ## 6. Cross-crate Registration
+///
+/// The linker section backing a store has the same visibility as the store
+/// itself, so a `pub` store can be stained from other crates. This lets
+/// plugins live in crates that depend on the crate defining the trait:
+///
+/// ```ignore
+/// // In `plugin_api`:
+/// create_stain! {
+///     trait Greeter;
+///     prefix: plugin_api;
+///     store: pub mod greeter_store;
+/// }
+///
+/// // In a plugin crate depending on `plugin_api`:
+/// use plugin_api::greeter_store;
+///
+/// stain! {
+///     store: greeter_store;
+///     item: English;
+///     ordering: 0;
+/// }
+/// ```
+///
+/// Implementations are only collected if the crate registering them is
+/// actually linked into the final binary. A plugin crate that's otherwise
+/// unused can be forced in with `use plugin_crate as _;`. As the section
+/// is shared by every crate in the binary, prefixing public stores (e.g.
+/// with the crate name) is recommended.
+///
+/// The trait can also be given by path, to create a store for a trait
+/// defined in another crate or module, e.g. `trait plugin_api::Greeter;`.
+///
+/// ## 7. Shared Context
+///
+/// Stores are built from statics, so they don't hold application state
+/// themselves. Instead, make the context a generic of the trait, declare
+/// it like any other generic, and pass it to each call:
+///
+/// ```rust
+/// use stain::{create_stain, stain, Store};
+///
+/// pub struct Request { pub path: &'static str }
+///
+/// pub trait Middleware<Ctx> {
+///     fn handle(&self, ctx: &Ctx) -> bool;
+/// }
+///
+/// create_stain! {
+///     trait Middleware;
+///     type Request;
+///     store: mod middleware_store;
+/// }
+///
+/// #[derive(Default)]
+/// struct BlockAdmin;
+/// impl Middleware<Request> for BlockAdmin {
+///     fn handle(&self, ctx: &Request) -> bool { !ctx.path.starts_with("/admin") }
+/// }
+///
+/// stain! {
+///     store: middleware_store;
+///     item: BlockAdmin;
+///     ordering: 0;
+/// }
+///
+/// fn main() {
+///     let store = middleware_store::Store::collect();
+///     let request = Request { path: "/admin/users" };
+///     assert!(!store.iter().all(|entry| entry.handle(&request)));
+/// }
+/// ```
+///
+/// ## 8. Trait Object Bounds
+///
+/// Trait objects in a store are `Send + Sync` by default. `bounds: ..;`
+/// (after the generics) replaces those bounds, e.g. to add a lifetime or
+/// the auto traits needed for panic isolation:
+///
+/// ```ignore
+/// use std::panic::{RefUnwindSafe, UnwindSafe};
+///
+/// create_stain! {
+///     trait Hook;
+///     bounds: Send + Sync + 'static + UnwindSafe + RefUnwindSafe;
+///     store: mod hook_store;
+/// }
+/// ```
+///
+/// As implementations are shared through statics, the bounds must still
+/// include `Send + Sync`; a store of `Send`-only trait objects doesn't
+/// compile:
+///
+/// ```compile_fail
+/// # use stain::create_stain;
+/// # pub trait Hook {}
+/// create_stain! {
+///     trait Hook;
+///     bounds: Send;
+///     store: mod hook_store;
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ## 9. Ordering Newtypes
+///
+/// Stores sharing an ordering type accept each other's orderings. Declaring
+/// the ordering as a tuple struct generates a newtype for it instead, so
+/// mixing up stores fails to compile:
+///
+/// ```compile_fail
+/// use stain::{create_stain, Store};
+///
+/// pub trait Hook {}
+/// pub trait Filter {}
+///
+/// create_stain! {
+///     trait Hook;
+///     ordering: pub struct HookOrdering(u32);
+///     store: mod hook_store;
+/// }
+///
+/// create_stain! {
+///     trait Filter;
+///     ordering: pub struct FilterOrdering(u32);
+///     store: mod filter_store;
+/// }
+///
+/// fn main() {
+///     let hooks = hook_store::Store::collect();
+///     hooks.ordering(&FilterOrdering(0));
+/// }
+/// ```
+///
+/// The newtype derives `Clone`, `Debug`, `PartialEq`, `Eq`, `PartialOrd`,
+/// `Ord` and `Hash`, and its field is public.
+///
+/// ## 10. Name Policies
+///
+/// Implementations can share a name, e.g. same-named types from different
+/// modules. `name_policy: first | last | error;` (after any bounds) picks
+/// which one [get_by_name](crate::Store::get_by_name) returns, or makes
+/// collecting the store panic. The default is `first`.
+///
+/// A type stained into the same store more than once only keeps one
+/// registration. `dedup_policy: keep_first | keep_last | error;` (after
+/// any name policy) picks which, by [Location](crate::Location) of the
+/// `stain! {...}` invocation, or makes collecting the store panic. The
+/// default is `keep_last`.
+///
+/// ## 11. Store Names
+///
+/// The generated struct is named `Store` by default. Another name can be
+/// given after the module, which reads better when several stores are
+/// imported together:
+///
+/// ```ignore
+/// create_stain! {
+///     trait Config;
+///     store: pub mod config as ConfigRegistry;
+/// }
+///
+/// let registry = config::ConfigRegistry::collect();
+/// ```
+///
+/// ## 12. Forwarding Methods
+///
+/// `forward: <method>, ..;` (after any policies) generates a method on the
+/// store for each listed trait method taking only `&self` and returning
+/// `()`, calling it on every implementation in order:
+///
+/// ```rust
+/// use stain::{create_stain, stain, Store};
+///
+/// pub trait Ticker { fn tick(&self); }
+///
+/// create_stain! {
+///     trait Ticker;
+///     forward: tick;
+///     store: mod ticker_store;
+/// }
+///
+/// #[derive(Default)]
+/// struct Clock;
+/// impl Ticker for Clock { fn tick(&self) {} }
+///
+/// stain! {
+///     store: ticker_store;
+///     item: Clock;
+///     ordering: 0;
+/// }
+///
+/// fn main() {
+///     ticker_store::Store::collect().tick();
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Invocations that don't match any of the syntaxes above fail with an
+/// error at the first unknown option or, if every option is known,
+/// listing the expected sections:
+///
+/// ```compile_fail
+/// # use stain::create_stain;
+/// # pub trait Hook {}
+/// create_stain! {
+///     trait Hook;
+///     stor: mod hook_store;
+/// }
+/// # fn main() {}
+/// ```
 #[macro_export]
 macro_rules! create_stain {
+    // Ordering newtype...
     (
-        // The trait for which the trait-object plugin store
-        // should be generated.
-        trait $trait:ident;
-        // Some type that can be ordered via Ord, used to
-        // enable ordered plugin execution.
-        //
-        // Customization is enabled so you can, for example,
-        // use runtime values (e.g. enums) to address specific plugins.
+        trait $($trait:ident)::+;
+        ordering: $vis:vis struct $newtype:ident($ordering:ty);
+
+        $($rest:tt)*
+    ) => {
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $newtype(pub $ordering);
+
+        $crate::create_stain! {
+            trait $($trait)::+;
+            ordering: $newtype;
+
+            $($rest)*
+        }
+    };
+
+    // Internal: defaults the name of the store struct...
+    (@named [$name:ident] $($rest:tt)*) => {
+        $crate::create_stain! { @store $name $($rest)* }
+    };
+    (@named [] $($rest:tt)*) => {
+        $crate::create_stain! { @store Store $($rest)* }
+    };
+
+    // Internal: the trait object type, `Send + Sync` unless
+    // `bounds: ..;` was given...
+    (@dyn [$($trait:tt)*] []) => { dyn $($trait)* + Send + Sync };
+    (@dyn [$($trait:tt)*] [$($bounds:tt)+]) => { dyn $($trait)* + $($bounds)+ };
+
+    // Internal: implements the store's `StainedBy` for every type that
+    // can be stained into it, for `stain_assert_impl!`...
+    (@stained_by $stained_by:ident $item:ident [$($trait:tt)*] []) => {
+        impl<T: $($trait)* + Send + Sync + 'static> $stained_by<$item> for T {}
+    };
+    (@stained_by $stained_by:ident $item:ident [$($trait:tt)*] [$($bounds:tt)+]) => {
+        impl<T: $($trait)* + $($bounds)+ + 'static> $stained_by<$item> for T {}
+    };
+
+    // Internal: the `NamePolicy` for `name_policy: ..;`...
+    (@name_policy first) => { $crate::NamePolicy::First };
+    (@name_policy last) => { $crate::NamePolicy::Last };
+    (@name_policy error) => { $crate::NamePolicy::Error };
+
+    // Internal: the `DedupPolicy` for `dedup_policy: ..;`...
+    (@dedup_policy keep_first) => { $crate::DedupPolicy::KeepFirst };
+    (@dedup_policy keep_last) => { $crate::DedupPolicy::KeepLast };
+    (@dedup_policy error) => { $crate::DedupPolicy::Error };
+
+    // Internal: generates the store struct `$name`, with `$vis` on the
+    // module and `$inner_vis` on the items in it that must be visible
+    // where the module is...
+    (
+        @store
+        $name:ident
+        [$($vis:tt)*]
+        [$($inner_vis:tt)*]
+
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
-        // Syntax for specifying trait generics.
         $(type $generic:ty;)*
-        // Syntax for specifying Generic Associated Types (GATs).
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        // An optional prefix that acts as a namespace
-        // for the [linkme] section.
         prefix$(: $prefix:ident)?;
-        // The module declaration for the generated module
-        // that will hold the generated store.
-        store: pub mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: $store:ident;
     ) => {
         $crate::paste! {
             #[doc(hidden)]
             #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ITEM >] = dyn $trait<
-                $($generic,)*
-                $($associated = $associated_type,)*
-            > + Send + Sync;
+            type [< __STAIN_ $store:upper _ITEM >] = $crate::create_stain!(
+                @dyn
+                [
+                    $($trait)::+<
+                        $($generic,)*
+                        $({ $const_generic },)*
+                        $($associated = $associated_type,)*
+                    >
+                ]
+                [$($($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*)?]
+            );
 
             #[doc(hidden)]
             #[allow(non_camel_case_types)]
             type [< __STAIN_ $store:upper _ORDERING >] = $ordering;
 
-            pub mod $store {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #[diagnostic::on_unimplemented(
+                message = "`{Self}` can't be stained into a store of `{I}`",
+                label = "doesn't implement the store's trait and bounds",
+                note = "stained types must implement the store's trait and its bounds (`Send + Sync` by default), and be `'static`"
+            )]
+            $($vis)* trait [< __STAIN_ $store:upper _STAINED_BY >]<I: ?Sized> {}
+
+            $crate::create_stain! {
+                @stained_by
+                [< __STAIN_ $store:upper _STAINED_BY >]
+                [< __STAIN_ $store:upper _ITEM >]
+                [
+                    $($trait)::+<
+                        $($generic,)*
+                        $({ $const_generic },)*
+                        $($associated = $associated_type,)*
+                    >
+                ]
+                [$($($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*)?]
+            }
+
+            $(#[$store_attr])*
+            $($vis)* mod $store {
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                $($inner_vis)* use super::[< __STAIN_ $store:upper _STAINED_BY >] as StainedBy;
                 #[doc(hidden)]
                 #[allow(non_camel_case_types)]
                 type __STAIN_ITEM = super::[< __STAIN_ $store:upper _ITEM >];
@@ -187,65 +512,91 @@ macro_rules! create_stain {
                 #[linkme(crate = $crate::linkme)]
                 #[doc(hidden)]
                 #[allow(non_upper_case_globals)]
-                pub static [< __STAIN_ $($prefix:upper)? _ $store:upper >]: [$crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>];
+                $($inner_vis)* static [< __STAIN_ $($prefix:upper)? _ $store:upper >]: [$crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>];
 
                 #[doc(hidden)]
-                pub use [< __STAIN_ $($prefix:upper)? _ $store:upper >] as __STAIN_COLLECTION;
+                $($inner_vis)* use [< __STAIN_ $($prefix:upper)? _ $store:upper >] as __STAIN_COLLECTION;
 
+                /// The implementations collected into this store.
                 #[derive(Clone)]
-                pub struct Store {
-                    entries: std::collections::BTreeMap<
-                        __STAIN_ORDERING,
-                        std::vec::Vec<&'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>>,
-                    >,
-                    type_map: std::collections::HashMap<
-                        std::any::TypeId,
-                        &'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>
-                    >,
+                $($inner_vis)* struct $name($crate::GeneratedStore<__STAIN_ORDERING, __STAIN_ITEM>);
+
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                $($inner_vis)* type __STAIN_STORE = $name;
+
+                /// The ordering type of this store.
+                #[allow(dead_code)]
+                $($inner_vis)* type Ordering = __STAIN_ORDERING;
+
+                impl $name {
+                    $($(
+                        #[doc = concat!("Calls `", stringify!($forward), "` on every implementation, in order.")]
+                        pub fn $forward(&self) {
+                            <Self as $crate::Store>::call_all(self, |item| item.$forward());
+                        }
+                    )+)?
                 }
 
-                impl $crate::Store for Store {
+                impl $crate::Store for $name {
                     // Define the associated types based on macro input
                     type Item = __STAIN_ITEM;
                     type Ordering = __STAIN_ORDERING;
 
+                    $(const NAME_POLICY: $crate::NamePolicy = $crate::create_stain!(@name_policy $name_policy);)?
+                    $(const DEDUP_POLICY: $crate::DedupPolicy = $crate::create_stain!(@dedup_policy $dedup_policy);)?
+
                     fn collect() -> Self {
+                        let mut store = Self($crate::GeneratedStore::new());
+                        store.collect_into();
+
+                        store
+                    }
+
+                    fn collect_into(&mut self) {
                         use std::ops::Deref;
-                        use $crate::itertools::Itertools;
-
-                        // Note: accessing the slice via the static name generated above
-                        let type_map = [< __STAIN_ $($prefix:upper)? _ $store:upper >].deref()
-                            .into_iter()
-                            .map(|entry| (entry.type_id(), entry))
-                            .collect::<std::collections::HashMap<
-                                std::any::TypeId,
-                                &'static $crate::Entry::<Self::Ordering, Self::Item>
-                            >>();
-
-                        let entries = type_map
-                            .values()
-                            .cloned()
-                            .sorted()
-                            .chunk_by(|entry| entry.ordering().clone())
-                            .into_iter()
-                            .map(|(ordering, entries)| (ordering, entries.collect()))
-                            .collect();
-
-                        Self {
-                            entries,
-                            type_map,
-                        }
+
+                        // Note: accessing the slice via its alias generated above
+                        self.0.collect_from(
+                            __STAIN_COLLECTION.deref().into_iter().enumerate(),
+                            <Self as $crate::Store>::NAME_POLICY,
+                            <Self as $crate::Store>::DEDUP_POLICY,
+                        );
+                    }
+
+                    fn collect_filtered<F>(f: F) -> Self
+                    where
+                        F: Fn(&$crate::Entry<Self::Ordering, Self::Item>) -> bool,
+                    {
+                        use std::ops::Deref;
+
+                        let mut store = Self($crate::GeneratedStore::new());
+                        store.0.collect_from(
+                            __STAIN_COLLECTION.deref()
+                                .into_iter()
+                                .enumerate()
+                                .filter(|(_, entry)| f(*entry)),
+                            <Self as $crate::Store>::NAME_POLICY,
+                            <Self as $crate::Store>::DEDUP_POLICY,
+                        );
+
+                        store
+                    }
+
+                    fn extend_with_store(&mut self, other: &Self) {
+                        self.0.extend_from(&other.0, <Self as $crate::Store>::DEDUP_POLICY);
                     }
 
                     fn iter(&self) -> impl std::iter::Iterator<
                         Item = $crate::EntryRef<'_, Self::Ordering, Self::Item>
                     > {
-                        self.entries
-                            .values()
-                            .map(|entries| entries.iter())
-                            .flatten()
-                            .map(|entry| *entry)
-                            .map($crate::EntryRef::from)
+                        self.0.iter()
+                    }
+
+                    fn iter_desc(&self) -> impl std::iter::Iterator<
+                        Item = $crate::EntryRef<'_, Self::Ordering, Self::Item>
+                    > {
+                        self.0.iter_desc()
                     }
 
                     fn ordering<'a>(&'a self, ordering: &Self::Ordering) -> Option<
@@ -253,21 +604,94 @@ macro_rules! create_stain {
                             Item = $crate::EntryRef<'a, Self::Ordering, Self::Item>
                         > + 'a
                     > {
-                        let entries = self.entries.get(ordering)?;
-                        Some(
-                            entries
-                                .iter()
-                                .map(|entry| *entry)
-                                .map($crate::EntryRef::from)
-                        )
+                        self.0.ordering(ordering)
+                    }
+
+                    fn ordering_at_or_above<'a>(&'a self, ordering: &Self::Ordering) -> impl std::iter::Iterator<
+                        Item = $crate::EntryRef<'a, Self::Ordering, Self::Item>
+                    > + 'a {
+                        self.0.ordering_at_or_above(ordering)
+                    }
+
+                    fn entries_slice(&self) -> &[$crate::EntryRef<'_, Self::Ordering, Self::Item>] {
+                        self.0.entries_slice()
+                    }
+
+                    fn name_index(&self) -> &std::collections::HashMap<
+                        &'static str,
+                        $crate::EntryRef<'_, Self::Ordering, Self::Item>,
+                    > {
+                        self.0.name_index()
+                    }
+
+                    fn contains_ordering(&self, ordering: &Self::Ordering) -> bool {
+                        self.0.contains_ordering(ordering)
+                    }
+
+                    fn ordering_bounds(&self) -> Option<(&Self::Ordering, &Self::Ordering)> {
+                        self.0.ordering_bounds()
                     }
 
                     fn concrete<T: std::any::Any + Send + Sync>(&self) -> Option<
                         $crate::ConcreteEntryRef<'_, T>
                     > {
-                        self.type_map
-                            .get(&std::any::TypeId::of::<T>())?
-                            .concrete::<T>()
+                        self.0.concrete::<T>()
+                    }
+
+                    fn try_concrete<T: std::any::Any + Send + Sync>(&self) -> Result<
+                        $crate::ConcreteEntryRef<'_, T>,
+                        $crate::ConcreteError,
+                    > {
+                        self.0.try_concrete::<T>()
+                    }
+
+                    fn single<T: std::any::Any + Send + Sync>(&self) -> Result<
+                        $crate::ConcreteEntryRef<'_, T>,
+                        $crate::SingleError,
+                    > {
+                        self.0.single::<T>()
+                    }
+
+                    fn count_total(&self) -> usize {
+                        self.0.count_total()
+                    }
+
+                    fn get_by_type_id(&self, type_id: std::any::TypeId) -> Option<
+                        $crate::EntryRef<'_, Self::Ordering, Self::Item>
+                    > {
+                        self.0.get_by_type_id(type_id)
+                    }
+
+                    fn key_of(
+                        &self,
+                        entry: &$crate::EntryRef<'_, Self::Ordering, Self::Item>,
+                    ) -> $crate::PluginKey<Self> {
+                        self.0.key_of(entry)
+                    }
+
+                    fn keys_of<T: std::any::Any>(&self) -> impl std::iter::Iterator<
+                        Item = $crate::PluginKey<Self>
+                    > + '_ {
+                        self.0.keys_of::<Self, T>()
+                    }
+
+                    fn get_by_key(&self, key: $crate::PluginKey<Self>) -> Option<
+                        $crate::EntryRef<'_, Self::Ordering, Self::Item>
+                    > {
+                        self.0.get_by_key(key)
+                    }
+
+                    fn get_registration(&self, key: $crate::PluginKey<Self>) -> Option<
+                        $crate::EntryRef<'_, Self::Ordering, Self::Item>
+                    > {
+                        self.0.get_registration(key)
+                    }
+
+                    fn retain<F>(&mut self, f: F)
+                    where
+                        F: FnMut(&$crate::EntryRef<'_, Self::Ordering, Self::Item>) -> bool,
+                    {
+                        self.0.retain(f)
                     }
                 }
             }
@@ -277,7 +701,7 @@ macro_rules! create_stain {
     (
         // The trait for which the trait-object plugin store
         // should be generated.
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         // Some type that can be ordered via Ord, used to
         // enable ordered plugin execution.
         //
@@ -287,131 +711,53 @@ macro_rules! create_stain {
 
         // Syntax for specifying trait generics.
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         // Syntax for specifying Generic Associated Types (GATs).
         $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         // An optional prefix that acts as a namespace
         // for the [linkme] section.
         prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
         // The module declaration for the generated module
         // that will hold the generated store.
-        store: pub(crate) mod $store:ident;
+        store: pub mod $store:ident $(as $name:ident)?;
     ) => {
-        $crate::paste! {
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ITEM >] = dyn $trait<
-                $($generic,)*
-                $($associated = $associated_type,)*
-            > + Send + Sync;
-
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ORDERING >] = $ordering;
-
-            pub(crate) mod $store {
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ITEM = super::[< __STAIN_ $store:upper _ITEM >];
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ORDERING = super::[<__STAIN_ $store:upper _ORDERING>];
-
-                #[$crate::linkme::distributed_slice]
-                #[linkme(crate = $crate::linkme)]
-                #[doc(hidden)]
-                #[allow(non_upper_case_globals)]
-                pub(crate) static [< __STAIN_ $($prefix:upper)? _ $store:upper >]: [$crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>];
-
-                #[doc(hidden)]
-                pub(crate) use [< __STAIN_ $($prefix:upper)? _ $store:upper >] as __STAIN_COLLECTION;
-
-                #[derive(Clone)]
-                pub(crate) struct Store {
-                    entries: std::collections::BTreeMap<
-                        __STAIN_ORDERING,
-                        std::vec::Vec<&'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>>,
-                    >,
-                    type_map: std::collections::HashMap<
-                        std::any::TypeId,
-                        &'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>
-                    >,
-                }
-
-                impl $crate::Store for Store {
-                    // Define the associated types based on macro input
-                    type Item = __STAIN_ITEM;
-                    type Ordering = __STAIN_ORDERING;
-
-                    fn collect() -> Self {
-                        use std::ops::Deref;
-                        use $crate::itertools::Itertools;
-
-                        // Note: accessing the slice via the static name generated above
-                        let type_map = [< __STAIN_ $($prefix:upper)? _ $store:upper >].deref()
-                            .into_iter()
-                            .map(|entry| (entry.type_id(), entry))
-                            .collect::<std::collections::HashMap<
-                                std::any::TypeId,
-                                &'static $crate::Entry::<Self::Ordering, Self::Item>
-                            >>();
-
-                        let entries = type_map
-                            .values()
-                            .cloned()
-                            .sorted()
-                            .chunk_by(|entry| entry.ordering().clone())
-                            .into_iter()
-                            .map(|(ordering, entries)| (ordering, entries.collect()))
-                            .collect();
-
-                        Self {
-                            entries,
-                            type_map,
-                        }
-                    }
+        $crate::create_stain! {
+            @named
+            [$($name)?]
+            [pub]
+            [pub]
 
-                    fn iter(&self) -> impl std::iter::Iterator<
-                        Item = $crate::EntryRef<'_, Self::Ordering, Self::Item>
-                    > {
-                        self.entries
-                            .values()
-                            .map(|entries| entries.iter())
-                            .flatten()
-                            .map(|entry| *entry)
-                            .map($crate::EntryRef::from)
-                    }
+            trait $($trait)::+;
+            ordering: $ordering;
 
-                    fn ordering<'a>(&'a self, ordering: &Self::Ordering) -> Option<
-                        impl std::iter::Iterator<
-                            Item = $crate::EntryRef<'a, Self::Ordering, Self::Item>
-                        > + 'a
-                    > {
-                        let entries = self.entries.get(ordering)?;
-                        Some(
-                            entries
-                                .iter()
-                                .map(|entry| *entry)
-                                .map($crate::EntryRef::from)
-                        )
-                    }
+            $(type $generic;)*
+            $(const $const_generic;)*
+            $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
-                    fn concrete<T: std::any::Any + Send + Sync>(&self) -> Option<
-                        $crate::ConcreteEntryRef<'_, T>
-                    > {
-                        self.type_map
-                            .get(&std::any::TypeId::of::<T>())?
-                            .concrete::<T>()
-                    }
-                }
-            }
+            prefix$(: $prefix)?;
+            $(#[$store_attr])*
+            store: $store;
         }
     };
 
     (
         // The trait for which the trait-object plugin store
         // should be generated.
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         // Some type that can be ordered via Ord, used to
         // enable ordered plugin execution.
         //
@@ -421,131 +767,53 @@ macro_rules! create_stain {
 
         // Syntax for specifying trait generics.
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         // Syntax for specifying Generic Associated Types (GATs).
         $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         // An optional prefix that acts as a namespace
         // for the [linkme] section.
         prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
         // The module declaration for the generated module
         // that will hold the generated store.
-        store: pub(super) mod $store:ident;
+        store: pub(crate) mod $store:ident $(as $name:ident)?;
     ) => {
-        $crate::paste! {
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ITEM >] = dyn $trait<
-                $($generic,)*
-                $($associated = $associated_type,)*
-            > + Send + Sync;
-
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ORDERING >] = $ordering;
-
-            pub(super) mod $store {
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ITEM = super::[< __STAIN_ $store:upper _ITEM >];
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ORDERING = super::[<__STAIN_ $store:upper _ORDERING>];
-
-                #[$crate::linkme::distributed_slice]
-                #[linkme(crate = $crate::linkme)]
-                #[doc(hidden)]
-                #[allow(non_upper_case_globals)]
-                pub(in super::super) static [< __STAIN_ $($prefix:upper)? _ $store:upper >]: [$crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>];
-
-                #[doc(hidden)]
-                pub(in super::super) use [< __STAIN_ $($prefix:upper)? _ $store:upper >] as __STAIN_COLLECTION;
-
-                #[derive(Clone)]
-                pub(in super::super) struct Store {
-                    entries: std::collections::BTreeMap<
-                        __STAIN_ORDERING,
-                        std::vec::Vec<&'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>>,
-                    >,
-                    type_map: std::collections::HashMap<
-                        std::any::TypeId,
-                        &'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>
-                    >,
-                }
-
-                impl $crate::Store for Store {
-                    // Define the associated types based on macro input
-                    type Item = __STAIN_ITEM;
-                    type Ordering = __STAIN_ORDERING;
-
-                    fn collect() -> Self {
-                        use std::ops::Deref;
-                        use $crate::itertools::Itertools;
-
-                        // Note: accessing the slice via the static name generated above
-                        let type_map = [< __STAIN_ $($prefix:upper)? _ $store:upper >].deref()
-                            .into_iter()
-                            .map(|entry| (entry.type_id(), entry))
-                            .collect::<std::collections::HashMap<
-                                std::any::TypeId,
-                                &'static $crate::Entry::<Self::Ordering, Self::Item>
-                            >>();
-
-                        let entries = type_map
-                            .values()
-                            .cloned()
-                            .sorted()
-                            .chunk_by(|entry| entry.ordering().clone())
-                            .into_iter()
-                            .map(|(ordering, entries)| (ordering, entries.collect()))
-                            .collect();
-
-                        Self {
-                            entries,
-                            type_map,
-                        }
-                    }
+        $crate::create_stain! {
+            @named
+            [$($name)?]
+            [pub(crate)]
+            [pub(crate)]
 
-                    fn iter(&self) -> impl std::iter::Iterator<
-                        Item = $crate::EntryRef<'_, Self::Ordering, Self::Item>
-                    > {
-                        self.entries
-                            .values()
-                            .map(|entries| entries.iter())
-                            .flatten()
-                            .map(|entry| *entry)
-                            .map($crate::EntryRef::from)
-                    }
+            trait $($trait)::+;
+            ordering: $ordering;
 
-                    fn ordering<'a>(&'a self, ordering: &Self::Ordering) -> Option<
-                        impl std::iter::Iterator<
-                            Item = $crate::EntryRef<'a, Self::Ordering, Self::Item>
-                        > + 'a
-                    > {
-                        let entries = self.entries.get(ordering)?;
-                        Some(
-                            entries
-                                .iter()
-                                .map(|entry| *entry)
-                                .map($crate::EntryRef::from)
-                        )
-                    }
+            $(type $generic;)*
+            $(const $const_generic;)*
+            $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
-                    fn concrete<T: std::any::Any + Send + Sync>(&self) -> Option<
-                        $crate::ConcreteEntryRef<'_, T>
-                    > {
-                        self.type_map
-                            .get(&std::any::TypeId::of::<T>())?
-                            .concrete::<T>()
-                    }
-                }
-            }
+            prefix$(: $prefix)?;
+            $(#[$store_attr])*
+            store: $store;
         }
     };
 
     (
         // The trait for which the trait-object plugin store
         // should be generated.
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         // Some type that can be ordered via Ord, used to
         // enable ordered plugin execution.
         //
@@ -555,131 +823,109 @@ macro_rules! create_stain {
 
         // Syntax for specifying trait generics.
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         // Syntax for specifying Generic Associated Types (GATs).
         $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         // An optional prefix that acts as a namespace
         // for the [linkme] section.
         prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
         // The module declaration for the generated module
         // that will hold the generated store.
-        store: mod $store:ident;
+        store: pub(super) mod $store:ident $(as $name:ident)?;
     ) => {
-        $crate::paste! {
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ITEM >] = dyn $trait<
-                $($generic,)*
-                $($associated = $associated_type,)*
-            > + Send + Sync;
-
-            #[doc(hidden)]
-            #[allow(non_camel_case_types)]
-            type [< __STAIN_ $store:upper _ORDERING >] = $ordering;
+        $crate::create_stain! {
+            @named
+            [$($name)?]
+            [pub(super)]
+            [pub(in super::super)]
 
-            mod $store {
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ITEM = super::[< __STAIN_ $store:upper _ITEM >];
-                #[doc(hidden)]
-                #[allow(non_camel_case_types)]
-                type __STAIN_ORDERING = super::[<__STAIN_ $store:upper _ORDERING>];
+            trait $($trait)::+;
+            ordering: $ordering;
 
-                #[$crate::linkme::distributed_slice]
-                #[linkme(crate = $crate::linkme)]
-                #[doc(hidden)]
-                #[allow(non_upper_case_globals)]
-                pub(super) static [< __STAIN_ $($prefix:upper)? _ $store:upper >]: [$crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>];
+            $(type $generic;)*
+            $(const $const_generic;)*
+            $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
-                #[doc(hidden)]
-                pub(super) use [< __STAIN_ $($prefix:upper)? _ $store:upper >] as __STAIN_COLLECTION;
+            prefix$(: $prefix)?;
+            $(#[$store_attr])*
+            store: $store;
+        }
+    };
 
-                #[derive(Clone)]
-                pub(super) struct Store {
-                    entries: std::collections::BTreeMap<
-                        __STAIN_ORDERING,
-                        std::vec::Vec<&'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>>,
-                    >,
-                    type_map: std::collections::HashMap<
-                        std::any::TypeId,
-                        &'static $crate::Entry::<__STAIN_ORDERING, __STAIN_ITEM>
-                    >,
-                }
+    (
+        // The trait for which the trait-object plugin store
+        // should be generated.
+        trait $($trait:ident)::+;
+        // Some type that can be ordered via Ord, used to
+        // enable ordered plugin execution.
+        //
+        // Customization is enabled so you can, for example,
+        // use runtime values (e.g. enums) to address specific plugins.
+        ordering: $ordering:ty;
 
-                impl $crate::Store for Store {
-                    // Define the associated types based on macro input
-                    type Item = __STAIN_ITEM;
-                    type Ordering = __STAIN_ORDERING;
+        // Syntax for specifying trait generics.
+        $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
+        // Syntax for specifying Generic Associated Types (GATs).
+        $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-                    fn collect() -> Self {
-                        use std::ops::Deref;
-                        use $crate::itertools::Itertools;
-
-                        // Note: accessing the slice via the static name generated above
-                        let type_map = [< __STAIN_ $($prefix:upper)? _ $store:upper >].deref()
-                            .into_iter()
-                            .map(|entry| (entry.type_id(), entry))
-                            .collect::<std::collections::HashMap<
-                                std::any::TypeId,
-                                &'static $crate::Entry::<Self::Ordering, Self::Item>
-                            >>();
-
-                        let entries = type_map
-                            .values()
-                            .cloned()
-                            .sorted()
-                            .chunk_by(|entry| entry.ordering().clone())
-                            .into_iter()
-                            .map(|(ordering, entries)| (ordering, entries.collect()))
-                            .collect();
-
-                        Self {
-                            entries,
-                            type_map,
-                        }
-                    }
+        // An optional prefix that acts as a namespace
+        // for the [linkme] section.
+        prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
+        // The module declaration for the generated module
+        // that will hold the generated store.
+        store: mod $store:ident $(as $name:ident)?;
+    ) => {
+        $crate::create_stain! {
+            @named
+            [$($name)?]
+            []
+            [pub(super)]
 
-                    fn iter(&self) -> impl std::iter::Iterator<
-                        Item = $crate::EntryRef<'_, Self::Ordering, Self::Item>
-                    > {
-                        self.entries
-                            .values()
-                            .map(|entries| entries.iter())
-                            .flatten()
-                            .map(|entry| *entry)
-                            .map($crate::EntryRef::from)
-                    }
+            trait $($trait)::+;
+            ordering: $ordering;
 
-                    fn ordering<'a>(&'a self, ordering: &Self::Ordering) -> Option<
-                        impl std::iter::Iterator<
-                            Item = $crate::EntryRef<'a, Self::Ordering, Self::Item>
-                        > + 'a
-                    > {
-                        let entries = self.entries.get(ordering)?;
-                        Some(
-                            entries
-                                .iter()
-                                .map(|entry| *entry)
-                                .map($crate::EntryRef::from)
-                        )
-                    }
+            $(type $generic;)*
+            $(const $const_generic;)*
+            $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
-                    fn concrete<T: std::any::Any + Send + Sync>(&self) -> Option<
-                        $crate::ConcreteEntryRef<'_, T>
-                    > {
-                        self.type_map
-                            .get(&std::any::TypeId::of::<T>())?
-                            .concrete::<T>()
-                    }
-                }
-            }
+            prefix$(: $prefix)?;
+            $(#[$store_attr])*
+            store: $store;
         }
     };
 
     (
         // The trait for which the trait-object plugin store
         // should be generated.
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         // Some type that can be ordered via Ord, used to
         // enable ordered plugin execution.
         //
@@ -689,32 +935,48 @@ macro_rules! create_stain {
 
         // Syntax for specifying trait generics.
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         // Syntax for specifying Generic Associated Types (GATs).
         $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         // An optional prefix that acts as a namespace
         // for the [linkme] section.
         prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
         // The module declaration for the generated module
         // that will hold the generated store.
-        store: pub(self) mod $store:ident;
+        store: pub(self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     (
         // The trait for which the trait-object plugin store
         // should be generated.
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         // Some type that can be ordered via Ord, used to
         // enable ordered plugin execution.
         //
@@ -724,495 +986,1219 @@ macro_rules! create_stain {
 
         // Syntax for specifying trait generics.
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         // Syntax for specifying Generic Associated Types (GATs).
         $(trait type $associated:ident = $associated_type:ty;)*
+        // Bounds on the trait object, replacing the default `Send + Sync`.
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        // How implementations sharing a name are resolved:
+        // `first` (default), `last` or `error`.
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         // An optional prefix that acts as a namespace
         // for the [linkme] section.
         prefix$(: $prefix:ident)?;
+        // Optional attributes (e.g. docs) for the generated module.
+        $(#[$store_attr:meta])*
         // The module declaration for the generated module
         // that will hold the generated store.
-        store: pub(in self) mod $store:ident;
+        store: pub(in self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional prefix...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional prefix (pub)...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub mod $store;
+            $(#[$store_attr])*
+            store: pub mod $store $(as $name)?;
         }
     };
 
     // Optional prefix (pub(crate))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(crate) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(crate) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub(crate) mod $store;
+            $(#[$store_attr])*
+            store: pub(crate) mod $store $(as $name)?;
         }
     };
 
     // Optional prefix (pub(super))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(super) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(super) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub(super) mod $store;
+            $(#[$store_attr])*
+            store: pub(super) mod $store $(as $name)?;
         }
     };
 
     // Optional prefix (pub(self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional prefix (pub(in self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
         ordering: $ordering:ty;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(in self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(in self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: $ordering;
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering (pub)...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: pub mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: pub mod $store;
+            $(#[$store_attr])*
+            store: pub mod $store $(as $name)?;
         }
     };
 
     // Optional ordering (pub(crate))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: pub(crate) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(crate) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: pub(crate) mod $store;
+            $(#[$store_attr])*
+            store: pub(crate) mod $store $(as $name)?;
         }
     };
 
     // Optional ordering (pub(super))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: pub(super) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(super) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: pub(super) mod $store;
+            $(#[$store_attr])*
+            store: pub(super) mod $store $(as $name)?;
         }
     };
 
     // Optional ordering (pub(self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: pub(self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering (pub(in self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
         prefix$(: $prefix:ident)?;
-        store: pub(in self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(in self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix$(: $prefix)?;
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix (pub)...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub mod $store;
+            $(#[$store_attr])*
+            store: pub mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix (pub (crate))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(crate) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(crate) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub(crate) mod $store;
+            $(#[$store_attr])*
+            store: pub(crate) mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix (pub(super))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(super) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(super) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: pub(super) mod $store;
+            $(#[$store_attr])*
+            store: pub(super) mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix (pub(self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
 
     // Optional ordering and optional prefix (pub(in self))...
     (
-        trait $trait:ident;
+        trait $($trait:ident)::+;
 
         $(type $generic:ty;)*
+        $(const $const_generic:expr;)*
         $(trait type $associated:ident = $associated_type:ty;)*
+        $(bounds: $($bound:ident)::* $($bound_lt:lifetime)? $(+ $($bounds:ident)::* $($bounds_lt:lifetime)?)*;)?
+        $(name_policy: $name_policy:ident;)?
+        $(dedup_policy: $dedup_policy:ident;)?
+        $(forward: $($forward:ident),+;)?
 
-        store: pub(in self) mod $store:ident;
+        $(#[$store_attr:meta])*
+        store: pub(in self) mod $store:ident $(as $name:ident)?;
     ) => {
         $crate::create_stain! {
-            trait $trait;
+            trait $($trait)::+;
             ordering: u64; // Injected default
 
             $(type $generic;)*
+            $(const $const_generic;)*
             $(trait type $associated = $associated_type;)*
+            $(bounds: $($bound)::* $($bound_lt)? $(+ $($bounds)::* $($bounds_lt)?)*;)?
+            $(name_policy: $name_policy;)?
+            $(dedup_policy: $dedup_policy;)?
+            $(forward: $($forward),+;)?
 
             prefix; // Injected empty prefix
-            store: mod $store;
+            $(#[$store_attr])*
+            store: mod $store $(as $name)?;
         }
     };
+
+    // Internal: looks for the first option that isn't known, to report it
+    // at the offending token. Invocations with only known options fall
+    // back to listing the expected sections.
+    (@invalid) => {
+        compile_error!(
+            "invalid `create_stain!` invocation, expected (in this order):\n\
+             \x20   trait <path::to::Trait>;\n\
+             \x20   ordering: <Type>;                              (optional, defaults to `u64`)\n\
+             \x20   type <Generic>;                                (zero or more)\n\
+             \x20   const <Value>;                                 (zero or more)\n\
+             \x20   trait type <Associated> = <Type>;              (zero or more)\n\
+             \x20   bounds: <Bound> + ..;                          (optional, defaults to `Send + Sync`)\n\
+             \x20   name_policy: first | last | error;             (optional, defaults to `first`)\n\
+             \x20   dedup_policy: keep_first | keep_last | error;  (optional, defaults to `keep_last`)\n\
+             \x20   forward: <method>, ..;                         (optional)\n\
+             \x20   prefix: <ident>;                               (optional)\n\
+             \x20   store: <visibility> mod <store> [as <Name>];"
+        );
+    };
+    (@invalid # [$($attr:tt)*] $($rest:tt)*) => {
+        $crate::create_stain!(@invalid $($rest)*);
+    };
+    (@invalid $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_key $key $key $($rest)*);
+    };
+    (@invalid_key trait $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key ordering $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key type $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key const $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key bounds $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key name_policy $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key dedup_policy $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key forward $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key prefix $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_key store $key:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    // Matched twice, as captured identifiers lose the span needed to
+    // report the error at them (see `stain!`'s `@check_name`).
+    (@invalid_key $ident:ident $key:tt $($rest:tt)*) => {
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            struct $key;
+
+            $crate::create_stain!(@invalid_key_type $key);
+        };
+    };
+    (@invalid_key $token:tt $key:tt $($rest:tt)*) => {
+        compile_error!(concat!(
+            "invalid `create_stain!` invocation, unexpected `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (@invalid_key_type $key:ty) => {
+        $crate::check_create_stain_option::<$key>();
+    };
+    (@invalid_skip ; $($rest:tt)*) => {
+        $crate::create_stain!(@invalid $($rest)*);
+    };
+    (@invalid_skip $token:tt $($rest:tt)*) => {
+        $crate::create_stain!(@invalid_skip $($rest)*);
+    };
+    (@invalid_skip) => {
+        $crate::create_stain!(@invalid);
+    };
+
+    // Anything else...
+    ($($tokens:tt)*) => {
+        $crate::create_stain!(@invalid $($tokens)*);
+    };
 }
 
+/// Stains (registers) an implementation into a store generated by [create_stain].
+///
+/// The implementation is constructed lazily, using [Default::default],
+/// the first time it's accessed through a collected store.
+///
+/// The store can be named by a path (e.g. `crate::plugins::hook_store`),
+/// so it doesn't have to be imported first.
+///
+/// The [name](crate::Entry::name) defaults to the item's name, and can be
+/// overridden with `name: "..";` after the item. For generic items (e.g.
+/// `Adapter<Json>`), `name: type_name;` uses [std::any::type_name] instead.
+///
+/// ```rust
+/// use stain::{create_stain, stain, Store};
+///
+/// pub trait Hook { fn run(&self) {} }
+///
+/// create_stain! {
+///     trait Hook;
+///     store: mod hook_store;
+/// }
+///
+/// #[derive(Default)]
+/// struct Audit;
+/// impl Hook for Audit {}
+///
+/// // `meta: { .. };` optionally attaches key/value pairs, read back
+/// // through `Entry::meta`. Repeating a key fails to compile.
+/// stain! {
+///     store: hook_store;
+///     item: Audit;
+///     ordering: 0;
+///     meta: { "desc" => "Audits every run" };
+/// }
+///
+/// // Unit structs can skip `#[derive(Default)]`; `derive: Default;`
+/// // implements it for them (and fails to compile for other structs).
+/// // It comes last, after any of the options above.
+/// struct Marker;
+/// impl Hook for Marker {}
+///
+/// stain! {
+///     store: hook_store;
+///     item: Marker;
+///     ordering: 1;
+///     derive: Default;
+/// }
+///
+/// fn main() {
+///     let store = hook_store::Store::collect();
+///     assert_eq!(store.iter().count(), 2);
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use stain::{create_stain, stain};
+/// # pub trait Hook {}
+/// # create_stain! { trait Hook; store: mod hook_store; }
+/// #[derive(Default)]
+/// struct Audit;
+/// impl Hook for Audit {}
+///
+/// stain! {
+///     store: hook_store;
+///     item: Audit;
+///     ordering: 0;
+///     meta: { "desc" => "one", "desc" => "two" };
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Types that validate invariants while constructing can implement
+/// [TryDefault](crate::TryDefault) instead of [Default], and be stained
+/// with `construct: try_default;` in place of `derive: Default;`. An error
+/// panics, naming the item, when the implementation is first accessed.
+///
+/// ```rust
+/// use stain::{create_stain, stain, Store, TryDefault};
+///
+/// pub trait Hook { fn limit(&self) -> usize; }
+///
+/// create_stain! {
+///     trait Hook;
+///     store: mod hook_store;
+/// }
+///
+/// struct Throttle { limit: usize }
+/// impl Hook for Throttle {
+///     fn limit(&self) -> usize { self.limit }
+/// }
+///
+/// impl TryDefault for Throttle {
+///     fn try_default() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+///         let limit = "8".parse()?;
+///         Ok(Self { limit })
+///     }
+/// }
+///
+/// stain! {
+///     store: hook_store;
+///     item: Throttle;
+///     ordering: 0;
+///     construct: try_default;
+/// }
+///
+/// fn main() {
+///     let store = hook_store::Store::collect();
+///     assert_eq!(store.iter().next().unwrap().limit(), 8);
+/// }
+/// ```
 #[macro_export]
 macro_rules! stain {
     (
         // The generated store. Used to get Store::Ordering
         // type for the static typing.
-        store: $store:ident;
+        store: $($store:ident)::+;
         // The concrete implementation/type to
         // stain/register in the collection.
-        item: $item:ident;
+        item: $item:ty;
+        // Optional name, instead of the stringified item: a string
+        // literal, or `type_name` for `std::any::type_name`.
+        $(name: $name:tt;)?
         // The ordering to apply to this implementation.
         ordering: $order:expr;
+        // Optional secondary key (a `u32`), ordering implementations
+        // that share an ordering. Lower tiebreaks come first.
+        $(tiebreak: $tiebreak:expr;)?
+        // Optionally marks the implementation as having no meaningful
+        // order relative to other unordered ones (`unordered: true;`).
+        $(unordered: $unordered:literal;)?
+        // Optional key/value metadata, exposed by `Entry::meta`.
+        $(meta: { $($key:literal => $value:literal),* $(,)? };)?
     ) => {
-        $crate::paste! {
-            #[$crate::rustversion::before(1.91)]
-            const _: () = {
-                use std::any::Any;
-                use std::sync::Arc;
-
-                fn __stain_init() -> (
-                    Arc<<$store::Store as $crate::Store>::Item>,
-                    Arc<dyn Any + Send + Sync>,
-                ) {
-                    let instance: $item = Default::default();
-                    let shared_instance = Arc::new(instance);
-
-                    let trait_view = shared_instance.clone() as Arc<<$store::Store as $crate::Store>::Item>;
-                    let any_view = shared_instance as Arc<dyn Any + Send + Sync>;
-
-                    (trait_view, any_view)
-                }
+        $crate::stain! {
+            @register
+            store: $($store)::+;
+            item: $item;
+            $(name: $name;)?
+            ordering: $order;
+            $(tiebreak: $tiebreak;)?
+            $(unordered: $unordered;)?
+            meta: &[$($(($key, $value)),*)?];
+            instance: std::sync::Arc::new(<$item as Default>::default());
+        }
+    };
 
-                #[$crate::linkme::distributed_slice($store::__STAIN_COLLECTION)]
-                #[linkme(crate = $crate::linkme)]
-                pub static _STAIN: $crate::Entry<
-                    <$store::Store as $crate::Store>::Ordering,
-                    <$store::Store as $crate::Store>::Item,
-                > =
-                $crate::Entry::<_,<$store::Store as $crate::Store>::Item>::new(
-                    || std::any::TypeId::of::<$item>(),
-                    $order,
-                    stringify!($item),
-                    __stain_init,
-                );
-            };
-
-            #[$crate::rustversion::since(1.91)]
-            const _: () = {
-                use std::any::Any;
-                use std::sync::Arc;
-
-                fn __stain_init() -> (
-                    Arc<<$store::Store as $crate::Store>::Item>,
-                    Arc<dyn Any + Send + Sync>,
-                ) {
-                    let instance: $item = Default::default();
-                    let shared_instance = Arc::new(instance);
-
-                    let trait_view = shared_instance.clone() as Arc<<$store::Store as $crate::Store>::Item>;
-                    let any_view = shared_instance as Arc<dyn Any + Send + Sync>;
-
-                    (trait_view, any_view)
-                }
+    // Internal: registers `$instance`, an `Arc<$item>` expression...
+    (
+        @register
+        store: $($store:ident)::+;
+        item: $item:ty;
+        $(name: $name:tt;)?
+        ordering: $order:expr;
+        $(tiebreak: $tiebreak:expr;)?
+        $(unordered: $unordered:literal;)?
+        meta: $meta:expr;
+        instance: $instance:expr;
+    ) => {
+        const _: () = assert!(
+            !$crate::has_duplicate_meta_keys($meta),
+            concat!("duplicate `meta` key in `stain!` for `", stringify!($item), "`"),
+        );
+        $($crate::stain!(@check_name $name);)?
+
+        #[$crate::rustversion::before(1.91)]
+        const _: () = {
+            use std::any::Any;
+            use std::sync::Arc;
+
+            fn __stain_init() -> (
+                Arc<<$($store)::+::__STAIN_STORE as $crate::Store>::Item>,
+                Arc<dyn Any + Send + Sync>,
+            ) {
+                let shared_instance: Arc<$item> = $instance;
+
+                let trait_view = shared_instance.clone() as Arc<<$($store)::+::__STAIN_STORE as $crate::Store>::Item>;
+                let any_view = shared_instance as Arc<dyn Any + Send + Sync>;
+
+                (trait_view, any_view)
+            }
 
-                #[$crate::linkme::distributed_slice($store::__STAIN_COLLECTION)]
-                #[linkme(crate = $crate::linkme)]
-                pub static _STAIN: $crate::Entry<
-                    <$store::Store as $crate::Store>::Ordering,
-                    <$store::Store as $crate::Store>::Item,
-                > =
-                $crate::Entry::<_,<$store::Store as $crate::Store>::Item>::new(
-                    std::any::TypeId::of::<$item>(),
-                    $order,
-                    stringify!($item),
-                    __stain_init,
-                );
-            };
+            #[$crate::linkme::distributed_slice($($store)::+::__STAIN_COLLECTION)]
+            #[linkme(crate = $crate::linkme)]
+            pub static _STAIN: $crate::Entry<
+                <$($store)::+::__STAIN_STORE as $crate::Store>::Ordering,
+                <$($store)::+::__STAIN_STORE as $crate::Store>::Item,
+            > =
+            $crate::Entry::<_,<$($store)::+::__STAIN_STORE as $crate::Store>::Item>::new(
+                || std::any::TypeId::of::<$item>(),
+                $order,
+                $crate::stain!(@name [$($name)?] $item),
+                __stain_init,
+            )
+            .with_location(file!(), line!(), column!())
+            .with_meta($meta)
+            $(.with_tiebreak($tiebreak))?
+            $(.with_unordered($unordered))?
+            .with_name_fn($crate::stain!(@name_fn [$($name)?] $item));
+        };
+
+        #[$crate::rustversion::since(1.91)]
+        const _: () = {
+            use std::any::Any;
+            use std::sync::Arc;
+
+            fn __stain_init() -> (
+                Arc<<$($store)::+::__STAIN_STORE as $crate::Store>::Item>,
+                Arc<dyn Any + Send + Sync>,
+            ) {
+                let shared_instance: Arc<$item> = $instance;
+
+                let trait_view = shared_instance.clone() as Arc<<$($store)::+::__STAIN_STORE as $crate::Store>::Item>;
+                let any_view = shared_instance as Arc<dyn Any + Send + Sync>;
+
+                (trait_view, any_view)
+            }
+
+            #[$crate::linkme::distributed_slice($($store)::+::__STAIN_COLLECTION)]
+            #[linkme(crate = $crate::linkme)]
+            pub static _STAIN: $crate::Entry<
+                <$($store)::+::__STAIN_STORE as $crate::Store>::Ordering,
+                <$($store)::+::__STAIN_STORE as $crate::Store>::Item,
+            > =
+            $crate::Entry::<_,<$($store)::+::__STAIN_STORE as $crate::Store>::Item>::new(
+                std::any::TypeId::of::<$item>(),
+                $order,
+                $crate::stain!(@name [$($name)?] $item),
+                __stain_init,
+            )
+            .with_location(file!(), line!(), column!())
+            .with_meta($meta)
+            $(.with_tiebreak($tiebreak))?
+            $(.with_unordered($unordered))?
+            .with_name_fn($crate::stain!(@name_fn [$($name)?] $item));
+        };
+    };
+
+    // Internal: the static name of an entry; a literal `name:` is used as is.
+    (@name [$name:literal] $item:ty) => {
+        $name
+    };
+    (@name [type_name] $item:ty) => {
+        stringify!($item)
+    };
+    (@name [] $item:ty) => {
+        stringify!($item)
+    };
+    // Rejected by `@check_name`.
+    (@name [$name:tt] $item:ty) => {
+        ""
+    };
+
+    // Internal: only `name: type_name;` needs resolving at runtime.
+    (@name_fn [type_name] $item:ty) => {
+        Some(std::any::type_name::<$item> as fn() -> &'static str)
+    };
+    (@name_fn [$($name:literal)?] $item:ty) => {
+        None
+    };
+    // Rejected by `@check_name`.
+    (@name_fn [$name:tt] $item:ty) => {
+        None
+    };
+
+    // Internal: rejects a `name:` that isn't a string literal or `type_name`.
+    (@check_name $name:literal) => {};
+    (@check_name type_name) => {};
+    (@check_name $name:tt) => {
+        $crate::stain!(@check_name $name $name);
+    };
+    // Other identifiers are rejected through a type named after them, so
+    // the error points at the name. They're matched twice, as captured
+    // identifiers lose the span needed for that.
+    (@check_name $ident:ident $name:tt) => {
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            struct $name;
+
+            $crate::stain!(@check_name_type $name);
+        };
+    };
+    (@check_name $token:tt $name:tt) => {
+        compile_error!(concat!(
+            "expected a string literal or `type_name` for `name:`, found `",
+            stringify!($name),
+            "`",
+        ));
+    };
+    (@check_name_type $name:ty) => {
+        $crate::check_name::<$name>();
+    };
+
+    // Default for unit structs...
+    (
+        store: $($store:ident)::+;
+        item: $item:ty;
+        $(name: $name:tt;)?
+        ordering: $order:expr;
+        $(tiebreak: $tiebreak:expr;)?
+        $(unordered: $unordered:literal;)?
+        $(meta: { $($key:literal => $value:literal),* $(,)? };)?
+        derive: Default;
+    ) => {
+        // Only compiles for unit structs, where `Self` is a value.
+        impl Default for $item {
+            fn default() -> Self {
+                Self
+            }
+        }
+
+        $crate::stain! {
+            store: $($store)::+;
+            item: $item;
+            $(name: $name;)?
+            ordering: $order;
+            $(tiebreak: $tiebreak;)?
+            $(unordered: $unordered;)?
+            $(meta: { $($key => $value),* };)?
+        }
+    };
+
+    // Fallible construction through `TryDefault`...
+    (
+        store: $($store:ident)::+;
+        item: $item:ty;
+        $(name: $name:tt;)?
+        ordering: $order:expr;
+        $(tiebreak: $tiebreak:expr;)?
+        $(unordered: $unordered:literal;)?
+        $(meta: { $($key:literal => $value:literal),* $(,)? };)?
+        construct: try_default;
+    ) => {
+        $crate::stain! {
+            @register
+            store: $($store)::+;
+            item: $item;
+            $(name: $name;)?
+            ordering: $order;
+            $(tiebreak: $tiebreak;)?
+            $(unordered: $unordered;)?
+            meta: &[$($(($key, $value)),*)?];
+            instance: std::sync::Arc::new(
+                <$item as $crate::TryDefault>::try_default()
+                    .unwrap_or_else(|error| {
+                        panic!("failed to construct `{}`: {error}", stringify!($item))
+                    }),
+            );
         }
     };
 }
+
+/// Stains (registers) a single instance of an implementation into several
+/// stores generated by [create_stain].
+///
+/// Unlike invoking [stain] once per store, which constructs an instance
+/// per store, every store shares the same lazily constructed instance, so
+/// state behind interior mutability is visible through all of them.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use stain::{create_stain, stain_shared, Store};
+///
+/// pub trait Record { fn record(&self); }
+/// pub trait Report { fn count(&self) -> usize; }
+///
+/// create_stain! { trait Record; store: mod record_store; }
+/// create_stain! { trait Report; store: mod report_store; }
+///
+/// #[derive(Default)]
+/// struct Counter(AtomicUsize);
+/// impl Record for Counter {
+///     fn record(&self) { self.0.fetch_add(1, Ordering::Relaxed); }
+/// }
+/// impl Report for Counter {
+///     fn count(&self) -> usize { self.0.load(Ordering::Relaxed) }
+/// }
+///
+/// stain_shared! {
+///     item: Counter;
+///     store: record_store;
+///     ordering: 0;
+///     store: report_store;
+///     ordering: 0;
+/// }
+///
+/// fn main() {
+///     record_store::Store::collect().iter().for_each(|entry| entry.record());
+///     let reports = report_store::Store::collect();
+///     assert_eq!(reports.iter().next().unwrap().count(), 1);
+/// }
+/// ```
+#[macro_export]
+macro_rules! stain_shared {
+    (
+        // The concrete implementation/type to
+        // stain/register in every store.
+        item: $item:ty;
+        // The stores, each with the ordering (and any of the optional
+        // `name`, `tiebreak`, `unordered` and `meta`) to apply in it.
+        $(
+            store: $($store:ident)::+;
+            $(name: $name:tt;)?
+            ordering: $order:expr;
+            $(tiebreak: $tiebreak:expr;)?
+            $(unordered: $unordered:literal;)?
+            $(meta: { $($key:literal => $value:literal),* $(,)? };)?
+        )+
+    ) => {
+        const _: () = {
+            #[allow(non_upper_case_globals)]
+            static __STAIN_SHARED: std::sync::LazyLock<std::sync::Arc<$item>> =
+                std::sync::LazyLock::new(|| std::sync::Arc::new(<$item as Default>::default()));
+
+            $(
+                $crate::stain! {
+                    @register
+                    store: $($store)::+;
+                    item: $item;
+                    $(name: $name;)?
+                    ordering: $order;
+                    $(tiebreak: $tiebreak;)?
+                    $(unordered: $unordered;)?
+                    meta: &[$($(($key, $value)),*)?];
+                    instance: __STAIN_SHARED.clone();
+                }
+            )+
+        };
+    };
+}
+
+/// Asserts at compile time that a type can be [stained](stain) into a store.
+///
+/// Registering a type that doesn't implement the store's trait fails deep
+/// inside the expansion of `stain! {...}`. This reports the missing trait
+/// (or `Send`/`Sync`) bound at the assertion instead, as "`Audit` can't be
+/// stained into a store of `(dyn Hook + Send + Sync + 'static)`".
+///
+/// ```rust
+/// use stain::{create_stain, stain_assert_impl};
+///
+/// pub trait Hook {}
+///
+/// create_stain! {
+///     trait Hook;
+///     store: mod hook_store;
+/// }
+///
+/// struct Audit;
+/// impl Hook for Audit {}
+///
+/// stain_assert_impl!(hook_store, Audit);
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// # use stain::{create_stain, stain_assert_impl};
+/// # pub trait Hook {}
+/// # create_stain! { trait Hook; store: mod hook_store; }
+/// // Doesn't implement `Hook`.
+/// struct Audit;
+///
+/// stain_assert_impl!(hook_store, Audit);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! stain_assert_impl {
+    ($($store:ident)::+, $item:ty $(,)?) => {
+        const _: () = {
+            fn __stain_assert_impl<I: ?Sized, T: $($store)::+::StainedBy<I>>() {}
+
+            // Reports the missing trait (or bound) through `StainedBy`.
+            #[allow(dead_code)]
+            fn __stain_assert() {
+                __stain_assert_impl::<
+                    <$($store)::+::__STAIN_STORE as $crate::Store>::Item,
+                    $item,
+                >()
+            }
+        };
+    };
+}
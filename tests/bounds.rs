@@ -0,0 +1,54 @@
+use std::panic::{RefUnwindSafe, UnwindSafe};
+
+use stain::{create_stain, stain, Store};
+
+trait Hook {
+    fn run(&self) -> u32;
+}
+
+// Branch: replaced bounds, with a lifetime and paths
+create_stain! {
+    trait Hook;
+    bounds: Send + Sync + 'static + std::panic::UnwindSafe + RefUnwindSafe;
+    store: mod hook_store;
+}
+
+// Branch: lifetime first
+create_stain! {
+    trait Hook;
+    bounds: 'static + Send + Sync;
+    store: mod static_store;
+}
+
+#[derive(Default)]
+struct Audit;
+
+impl Hook for Audit {
+    fn run(&self) -> u32 {
+        7
+    }
+}
+
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 0;
+}
+
+stain! {
+    store: static_store;
+    item: Audit;
+    ordering: 0;
+}
+
+fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe + ?Sized>(_: &T) {}
+
+#[test]
+fn test_bounds() {
+    let store = hook_store::Store::collect();
+    let entry = store.iter().next().unwrap();
+
+    assert_unwind_safe(&**entry);
+    assert_eq!(entry.run(), 7);
+    assert_eq!(static_store::Store::collect().iter().count(), 1);
+}
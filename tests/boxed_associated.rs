@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use stain::{create_stain, stain, Store};
+
+trait Converter<Input> {
+    type Output;
+
+    fn convert(&self, input: Input) -> Self::Output;
+}
+
+// Branch: associated type bound to a boxed trait object
+create_stain! {
+    trait Converter;
+
+    type u32;
+    trait type Output = Box<dyn Display>;
+
+    store: mod converter_store;
+}
+
+#[derive(Default)]
+struct Hex;
+
+impl Converter<u32> for Hex {
+    type Output = Box<dyn Display>;
+
+    fn convert(&self, input: u32) -> Self::Output {
+        Box::new(format!("{input:#x}"))
+    }
+}
+
+stain! {
+    store: converter_store;
+    item: Hex;
+    ordering: 0;
+}
+
+#[test]
+fn test_boxed_associated() {
+    let store = converter_store::Store::collect();
+    let converter = store.iter().next().unwrap();
+
+    assert_eq!(converter.convert(255).to_string(), "0xff");
+}
@@ -0,0 +1,44 @@
+use stain::{create_stain, stain, Store};
+
+trait Capability {
+    fn base(&self) -> &'static str;
+
+    // Disabled in every configuration.
+    #[cfg(any())]
+    fn gated(&self) -> &'static str;
+
+    #[cfg(not(any()))]
+    fn fallback(&self) -> &'static str {
+        "fallback"
+    }
+}
+
+// Branch: trait with cfg-gated methods
+create_stain! {
+    trait Capability;
+    store: mod capability_store;
+}
+
+#[derive(Default)]
+struct Basic;
+
+impl Capability for Basic {
+    fn base(&self) -> &'static str {
+        "base"
+    }
+}
+
+stain! {
+    store: capability_store;
+    item: Basic;
+    ordering: 0;
+}
+
+#[test]
+fn test_cfg_method() {
+    let store = capability_store::Store::collect();
+    let capability = store.iter().next().unwrap();
+
+    assert_eq!(capability.base(), "base");
+    assert_eq!(capability.fallback(), "fallback");
+}
@@ -0,0 +1,45 @@
+use stain::{create_stain, stain, Store};
+
+trait Limit {
+    fn limit(&self) -> u32;
+}
+
+create_stain! {
+    trait Limit;
+    store: mod limit_store;
+}
+
+// Branch: a `Clone` implementation pulled out by value
+#[derive(Clone)]
+struct RateLimit {
+    per_second: u32,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self { per_second: 10 }
+    }
+}
+
+impl Limit for RateLimit {
+    fn limit(&self) -> u32 {
+        self.per_second
+    }
+}
+
+stain! {
+    store: limit_store;
+    item: RateLimit;
+    ordering: 0;
+}
+
+#[test]
+fn test_concrete_cloned() {
+    let store = limit_store::Store::collect();
+
+    let mut cloned = store.concrete_cloned::<RateLimit>().unwrap();
+    cloned.per_second = 100;
+
+    assert_eq!(cloned.limit(), 100);
+    assert_eq!(store.iter().next().unwrap().limit(), 10);
+}
@@ -0,0 +1,60 @@
+use stain::{create_stain, stain, Store};
+
+trait Buffer<const N: usize> {
+    fn fill(&self) -> [u8; N];
+}
+
+// Branch: const generic parameter
+create_stain! {
+    trait Buffer;
+    const 16;
+    store: mod buffers;
+}
+
+#[derive(Default)]
+struct Zeroed;
+
+impl Buffer<16> for Zeroed {
+    fn fill(&self) -> [u8; 16] {
+        [0; 16]
+    }
+}
+
+stain! {
+    store: buffers;
+    item: Zeroed;
+    ordering: 0;
+}
+
+trait Frame<T, const N: usize> {
+    fn frame(&self, value: T) -> [T; N];
+}
+
+// Branch: type and const generic parameters
+create_stain! {
+    trait Frame;
+    type u8;
+    const 4;
+    store: mod frames;
+}
+
+impl Frame<u8, 4> for Zeroed {
+    fn frame(&self, value: u8) -> [u8; 4] {
+        [value; 4]
+    }
+}
+
+stain! {
+    store: frames;
+    item: Zeroed;
+    ordering: 0;
+}
+
+#[test]
+fn test_const_generic() {
+    let buffers = buffers::Store::collect();
+    let frames = frames::Store::collect();
+
+    assert_eq!(buffers.iter().next().unwrap().fill(), [0; 16]);
+    assert_eq!(frames.iter().next().unwrap().frame(7), [7; 4]);
+}
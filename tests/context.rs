@@ -0,0 +1,59 @@
+use stain::{create_stain, stain, Store};
+
+struct AppContext {
+    user: &'static str,
+    admin: bool,
+}
+
+trait Middleware<Ctx> {
+    fn handle(&self, ctx: &Ctx) -> String;
+}
+
+// Branch: shared context threaded through a trait generic
+create_stain! {
+    trait Middleware;
+    type AppContext;
+    store: mod middleware_store;
+}
+
+#[derive(Default)]
+struct Greet;
+
+impl Middleware<AppContext> for Greet {
+    fn handle(&self, ctx: &AppContext) -> String {
+        format!("hello {}", ctx.user)
+    }
+}
+
+stain! {
+    store: middleware_store;
+    item: Greet;
+    ordering: 0;
+}
+
+#[derive(Default)]
+struct Role;
+
+impl Middleware<AppContext> for Role {
+    fn handle(&self, ctx: &AppContext) -> String {
+        if ctx.admin { "admin" } else { "user" }.to_string()
+    }
+}
+
+stain! {
+    store: middleware_store;
+    item: Role;
+    ordering: 1;
+}
+
+#[test]
+fn test_context() {
+    let store = middleware_store::Store::collect();
+    let ctx = AppContext {
+        user: "ferris",
+        admin: true,
+    };
+
+    let responses: Vec<_> = store.iter().map(|entry| entry.handle(&ctx)).collect();
+    assert_eq!(responses, ["hello ferris", "admin"]);
+}
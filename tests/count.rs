@@ -0,0 +1,44 @@
+use stain::{create_stain, stain, Store};
+
+trait Hook {}
+
+create_stain! {
+    trait Hook;
+    store: mod hook_store;
+}
+
+#[derive(Default)]
+struct Audit;
+impl Hook for Audit {}
+
+#[derive(Default)]
+struct Trace;
+impl Hook for Trace {}
+
+// Branch: the same type registered twice
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 0;
+}
+
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 1;
+}
+
+stain! {
+    store: hook_store;
+    item: Trace;
+    ordering: 2;
+}
+
+#[test]
+fn test_count() {
+    let store = hook_store::Store::collect();
+
+    // Repeated registrations are counted, but deduplicated by type.
+    assert_eq!(store.count_total(), 3);
+    assert_eq!(store.count_unique_types(), 2);
+}
@@ -0,0 +1,26 @@
+use stain::{stain, Store};
+use stain_upstream_fixture::{greeter_store, Greeter};
+
+// Branch: registering into a store defined in another crate
+#[derive(Default)]
+struct Downstream;
+
+impl Greeter for Downstream {
+    fn greet(&self) -> &'static str {
+        "Hello from downstream!"
+    }
+}
+
+stain! {
+    store: greeter_store;
+    item: Downstream;
+    ordering: 1;
+}
+
+#[test]
+fn test_cross_crate() {
+    let store = greeter_store::Store::collect();
+    let greetings = store.iter().map(|greeter| greeter.greet()).collect::<Vec<_>>();
+
+    assert_eq!(greetings, ["Hello from upstream!", "Hello from downstream!"]);
+}
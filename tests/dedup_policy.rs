@@ -0,0 +1,58 @@
+use stain::{create_stain, stain, DedupPolicy, Store};
+
+trait Hook {}
+
+#[derive(Default)]
+struct Audit;
+impl Hook for Audit {}
+
+// Branch: default policy
+create_stain! {
+    trait Hook;
+    store: mod last_store;
+}
+
+stain! { store: last_store; item: Audit; ordering: 0; }
+stain! { store: last_store; item: Audit; ordering: 1; }
+
+// Branch: `keep_first` policy
+create_stain! {
+    trait Hook;
+    dedup_policy: keep_first;
+    store: mod first_store;
+}
+
+stain! { store: first_store; item: Audit; ordering: 0; }
+stain! { store: first_store; item: Audit; ordering: 1; }
+
+// Branch: `error` policy
+create_stain! {
+    trait Hook;
+    name_policy: first;
+    dedup_policy: error;
+    store: mod error_store;
+}
+
+stain! { store: error_store; item: Audit; ordering: 0; }
+stain! { store: error_store; item: Audit; ordering: 1; }
+
+#[test]
+fn test_dedup_policy_keep_last() {
+    let store = last_store::Store::collect();
+
+    assert_eq!(last_store::Store::DEDUP_POLICY, DedupPolicy::KeepLast);
+    assert_eq!(store.iter().map(|entry| *entry.ordering()).collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn test_dedup_policy_keep_first() {
+    let store = first_store::Store::collect();
+
+    assert_eq!(store.iter().map(|entry| *entry.ordering()).collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+#[should_panic(expected = "`Audit` is stained more than once")]
+fn test_dedup_policy_error() {
+    error_store::Store::collect();
+}
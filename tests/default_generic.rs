@@ -0,0 +1,39 @@
+use stain::{create_stain, stain, Store};
+
+trait Converter<Input = String> {
+    type Output;
+
+    fn convert(&self, input: Input) -> Self::Output;
+}
+
+// Branch: defaulted generic omitted, falling back to `String`
+create_stain! {
+    trait Converter;
+    trait type Output = usize;
+    store: mod converter_store;
+}
+
+#[derive(Default)]
+struct StringLen;
+
+impl Converter for StringLen {
+    type Output = usize;
+
+    fn convert(&self, input: String) -> usize {
+        input.len()
+    }
+}
+
+stain! {
+    store: converter_store;
+    item: StringLen;
+    ordering: 0;
+}
+
+#[test]
+fn test_default_generic() {
+    let store = converter_store::Store::collect();
+    let converter = store.iter().next().unwrap();
+
+    assert_eq!(converter.convert("stain".to_string()), 5);
+}
@@ -0,0 +1,46 @@
+use stain::{create_stain, stain, Store};
+
+trait Marker {}
+
+create_stain! {
+    trait Marker;
+    store: mod marker_store;
+}
+
+// Branch: unit struct without a manual `Default`
+struct UnitMarker;
+impl Marker for UnitMarker {}
+
+stain! {
+    store: marker_store;
+    item: UnitMarker;
+    ordering: 0;
+    derive: Default;
+}
+
+// Branch: unit struct with options
+struct NamedMarker;
+impl Marker for NamedMarker {}
+
+stain! {
+    store: marker_store;
+    item: NamedMarker;
+    name: "named";
+    ordering: 1;
+    unordered: true;
+    meta: { "kind" => "unit" };
+    derive: Default;
+}
+
+#[test]
+fn test_derive_default() {
+    let store = marker_store::Store::collect();
+    assert_eq!(store.iter().count(), 2);
+    assert!(store.concrete::<UnitMarker>().is_some());
+
+    let named = store.concrete::<NamedMarker>().unwrap();
+    assert_eq!(named.name(), "named");
+    let named = store.get_by_name("named").unwrap();
+    assert!(named.is_unordered());
+    assert_eq!(named.meta(), &[("kind", "unit")]);
+}
@@ -0,0 +1,68 @@
+use stain::{create_stain, stain, Store};
+
+trait Step {
+    fn run(&self) -> u32;
+}
+
+create_stain! {
+    trait Step;
+    store: mod step_store;
+}
+
+#[derive(Default)]
+struct First;
+
+impl Step for First {
+    fn run(&self) -> u32 {
+        1
+    }
+}
+
+stain! {
+    store: step_store;
+    item: First;
+    ordering: 0;
+}
+
+// Branch: an implementation that panics
+#[derive(Default)]
+struct Crash;
+
+impl Step for Crash {
+    fn run(&self) -> u32 {
+        panic!("crashed");
+    }
+}
+
+stain! {
+    store: step_store;
+    item: Crash;
+    ordering: 1;
+}
+
+#[derive(Default)]
+struct Last;
+
+impl Step for Last {
+    fn run(&self) -> u32 {
+        3
+    }
+}
+
+stain! {
+    store: step_store;
+    item: Last;
+    ordering: 2;
+}
+
+#[test]
+fn test_dispatch() {
+    let store = step_store::Store::collect();
+    let results = store.dispatch(|step| step.run());
+
+    assert_eq!(results[0], Ok(1));
+    let panic = results[1].as_ref().unwrap_err();
+    assert_eq!(panic.name(), "Crash");
+    assert_eq!(panic.message(), Some("crashed"));
+    assert_eq!(results[2], Ok(3));
+}
@@ -0,0 +1,34 @@
+//! Test fixture: a crate that owns a store other crates register into.
+//!
+//! Public stores must compile in crates that deny missing docs.
+#![deny(missing_docs)]
+
+use stain::{create_stain, stain};
+
+/// Greets whoever runs the tests.
+pub trait Greeter {
+    /// The greeting.
+    fn greet(&self) -> &'static str;
+}
+
+create_stain! {
+    trait Greeter;
+    prefix: stain_upstream_fixture;
+    /// Every registered [Greeter].
+    store: pub mod greeter_store;
+}
+
+#[derive(Default)]
+struct Upstream;
+
+impl Greeter for Upstream {
+    fn greet(&self) -> &'static str {
+        "Hello from upstream!"
+    }
+}
+
+stain! {
+    store: greeter_store;
+    item: Upstream;
+    ordering: 0;
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use stain::{create_stain, stain, Store};
+
+static TICKS: AtomicUsize = AtomicUsize::new(0);
+static RESETS: AtomicUsize = AtomicUsize::new(0);
+
+trait Ticker {
+    fn tick(&self);
+    fn reset(&self);
+}
+
+// Branch: forwarded methods
+create_stain! {
+    trait Ticker;
+    forward: tick, reset;
+    store: mod tickers;
+}
+
+#[derive(Default)]
+struct Clock;
+
+impl Ticker for Clock {
+    fn tick(&self) {
+        TICKS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn reset(&self) {
+        RESETS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Default)]
+struct Timer;
+
+impl Ticker for Timer {
+    fn tick(&self) {
+        TICKS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn reset(&self) {
+        RESETS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+stain! {
+    store: tickers;
+    item: Clock;
+    ordering: 0;
+}
+
+stain! {
+    store: tickers;
+    item: Timer;
+    ordering: 1;
+}
+
+#[test]
+fn test_forward() {
+    let store = tickers::Store::collect();
+
+    store.tick();
+    store.tick();
+    store.reset();
+    store.call_all(|ticker| ticker.reset());
+
+    assert_eq!(TICKS.load(Ordering::SeqCst), 4);
+    assert_eq!(RESETS.load(Ordering::SeqCst), 4);
+}
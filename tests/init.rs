@@ -0,0 +1,124 @@
+use stain::{create_stain, stain, Store};
+
+trait Plugin {
+    fn id(&self) -> &'static str;
+}
+
+create_stain! {
+    trait Plugin;
+    store: mod plugins;
+}
+
+#[derive(Default)]
+struct Alpha;
+
+impl Plugin for Alpha {
+    fn id(&self) -> &'static str {
+        "alpha"
+    }
+}
+
+#[derive(Default)]
+struct Beta;
+
+impl Plugin for Beta {
+    fn id(&self) -> &'static str {
+        "beta"
+    }
+}
+
+stain! {
+    store: plugins;
+    item: Alpha;
+    ordering: 0;
+}
+
+stain! {
+    store: plugins;
+    item: Beta;
+    ordering: 1;
+}
+
+create_stain! {
+    trait Plugin;
+    store: mod sparse;
+}
+
+stain! {
+    store: sparse;
+    item: Alpha;
+    ordering: 0;
+}
+
+stain! {
+    store: sparse;
+    item: Beta;
+    ordering: 1;
+}
+
+#[test]
+fn test_iter_lazy() {
+    let store = sparse::Store::collect();
+
+    let touched = store
+        .iter()
+        .filter(|entry| *entry.ordering() == 1)
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(touched, ["beta"]);
+
+    let initialized = store
+        .iter()
+        .map(|entry| (entry.name(), entry.is_initialized()))
+        .collect::<Vec<_>>();
+    assert_eq!(initialized, [("Alpha", false), ("Beta", true)]);
+}
+
+#[test]
+fn test_init_all() {
+    let store = plugins::Store::collect();
+
+    assert!(!store.any_initialized());
+    assert!(store.init_all().is_ok());
+    assert!(store.any_initialized());
+    assert!(store.iter().all(|entry| entry.is_initialized()));
+    assert_eq!(
+        store.iter().map(|entry| entry.id()).collect::<Vec<_>>(),
+        ["alpha", "beta"]
+    );
+}
+
+create_stain! {
+    trait Plugin;
+    store: mod broken;
+}
+
+struct Faulty;
+
+impl Default for Faulty {
+    fn default() -> Self {
+        panic!("missing config")
+    }
+}
+
+impl Plugin for Faulty {
+    fn id(&self) -> &'static str {
+        "faulty"
+    }
+}
+
+stain! {
+    store: broken;
+    item: Faulty;
+    ordering: 0;
+}
+
+#[test]
+fn test_init_all_panic() {
+    let store = broken::Store::collect();
+    let panic = store.init_all().unwrap_err();
+
+    assert_eq!(panic.name(), "Faulty");
+    assert_eq!(panic.message(), Some("missing config"));
+    assert!(!store.any_initialized());
+}
@@ -0,0 +1,49 @@
+use stain::{create_stain, stain, Store};
+
+trait Documented {}
+
+create_stain! {
+    trait Documented;
+    store: mod documented_store;
+}
+
+// Branch: metadata attached at registration
+#[derive(Default)]
+struct Described;
+impl Documented for Described {}
+
+stain! {
+    store: documented_store;
+    item: Described;
+    ordering: 0;
+    meta: {
+        "desc" => "A described plugin",
+        "version" => "1.0",
+    };
+}
+
+// Branch: no metadata
+#[derive(Default)]
+struct Bare;
+impl Documented for Bare {}
+
+stain! {
+    store: documented_store;
+    item: Bare;
+    ordering: 1;
+}
+
+#[test]
+fn test_meta() {
+    let store = documented_store::Store::collect();
+    let mut store_iter = store.iter();
+
+    let described = store_iter.next().unwrap();
+    assert_eq!(
+        described.meta(),
+        &[("desc", "A described plugin"), ("version", "1.0")]
+    );
+
+    let bare = store_iter.next().unwrap();
+    assert!(bare.meta().is_empty());
+}
@@ -0,0 +1,73 @@
+use stain::{create_stain, stain, NamePolicy, Store};
+
+trait Hook {
+    fn id(&self) -> u32;
+}
+
+macro_rules! hook {
+    ($item:ident, $id:literal) => {
+        #[derive(Default)]
+        struct $item;
+
+        impl Hook for $item {
+            fn id(&self) -> u32 {
+                $id
+            }
+        }
+    };
+}
+
+hook!(One, 1);
+hook!(Two, 2);
+
+// Branch: default policy
+create_stain! {
+    trait Hook;
+    store: mod first_store;
+}
+
+stain! { store: first_store; item: One; name: "hook"; ordering: 0; }
+stain! { store: first_store; item: Two; name: "hook"; ordering: 1; }
+
+// Branch: `last` policy
+create_stain! {
+    trait Hook;
+    name_policy: last;
+    store: mod last_store;
+}
+
+stain! { store: last_store; item: One; name: "hook"; ordering: 0; }
+stain! { store: last_store; item: Two; name: "hook"; ordering: 1; }
+
+// Branch: `error` policy
+create_stain! {
+    trait Hook;
+    name_policy: error;
+    store: mod error_store;
+}
+
+stain! { store: error_store; item: One; name: "hook"; ordering: 0; }
+stain! { store: error_store; item: Two; name: "hook"; ordering: 1; }
+
+#[test]
+fn test_name_policy_first() {
+    let store = first_store::Store::collect();
+
+    assert_eq!(first_store::Store::NAME_POLICY, NamePolicy::First);
+    assert_eq!(store.get_by_name("hook").unwrap().id(), 1);
+    assert_eq!(store.iter().count(), 2);
+}
+
+#[test]
+fn test_name_policy_last() {
+    let store = last_store::Store::collect();
+
+    assert_eq!(store.get_by_name("hook").unwrap().id(), 2);
+    assert_eq!(store.name_index()["hook"].id(), 2);
+}
+
+#[test]
+#[should_panic(expected = "`hook` is registered more than once")]
+fn test_name_policy_error() {
+    error_store::Store::collect();
+}
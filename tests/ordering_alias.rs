@@ -0,0 +1,59 @@
+use stain::{create_stain, stain, Store};
+
+trait Task {
+    fn run(&self) -> &'static str;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Priority {
+    Low,
+    High,
+}
+
+create_stain! {
+    trait Task;
+    ordering: Priority;
+    store: mod tasks;
+}
+
+#[derive(Default)]
+struct Cleanup;
+
+impl Task for Cleanup {
+    fn run(&self) -> &'static str {
+        "cleanup"
+    }
+}
+
+#[derive(Default)]
+struct Startup;
+
+impl Task for Startup {
+    fn run(&self) -> &'static str {
+        "startup"
+    }
+}
+
+// Branch: ordering through the store's alias
+stain! {
+    store: tasks;
+    item: Cleanup;
+    ordering: tasks::Ordering::Low;
+}
+
+stain! {
+    store: tasks;
+    item: Startup;
+    ordering: tasks::Ordering::High;
+}
+
+#[test]
+fn test_ordering_alias() {
+    let store = tasks::Store::collect();
+
+    assert_eq!(
+        store.iter_desc().map(|task| task.run()).collect::<Vec<_>>(),
+        ["startup", "cleanup"]
+    );
+    assert!(store.contains_ordering(&Priority::High));
+}
@@ -0,0 +1,56 @@
+use stain::{create_stain, stain, Store};
+
+trait Hook {}
+
+create_stain! {
+    trait Hook;
+    ordering: i32;
+    store: mod hook_store;
+}
+
+#[derive(Default)]
+struct Early;
+impl Hook for Early {}
+
+#[derive(Default)]
+struct Middle;
+impl Hook for Middle {}
+
+#[derive(Default)]
+struct Late;
+impl Hook for Late {}
+
+// Branch: negative ordering
+stain! {
+    store: hook_store;
+    item: Early;
+    ordering: -5;
+}
+
+stain! {
+    store: hook_store;
+    item: Middle;
+    ordering: 0;
+}
+
+stain! {
+    store: hook_store;
+    item: Late;
+    ordering: 10;
+}
+
+#[test]
+fn test_ordering_bounds() {
+    let store = hook_store::Store::collect();
+    assert_eq!(store.ordering_bounds(), Some((&-5, &10)));
+
+    // `-5..10` includes its start, and excludes its end.
+    let store = hook_store::Store::collect_filtered(|entry| (-5..10).contains(entry.ordering()));
+    assert_eq!(store.ordering_bounds(), Some((&-5, &0)));
+
+    let store = hook_store::Store::collect_filtered(|entry| (0..10).contains(entry.ordering()));
+    assert_eq!(store.ordering_bounds(), Some((&0, &0)));
+
+    let store = hook_store::Store::collect_filtered(|entry| (1..10).contains(entry.ordering()));
+    assert_eq!(store.ordering_bounds(), None);
+}
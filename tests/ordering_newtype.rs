@@ -0,0 +1,41 @@
+use stain::{create_stain, stain, Store};
+
+trait Hook {}
+
+// Branch: ordering declared as a generated newtype
+create_stain! {
+    trait Hook;
+    ordering: pub struct HookOrdering(u32);
+    store: mod hook_store;
+}
+
+#[derive(Default)]
+struct Early;
+impl Hook for Early {}
+
+stain! {
+    store: hook_store;
+    item: Early;
+    ordering: HookOrdering(1);
+}
+
+#[derive(Default)]
+struct Late;
+impl Hook for Late {}
+
+stain! {
+    store: hook_store;
+    item: Late;
+    ordering: HookOrdering(5);
+}
+
+#[test]
+fn test_ordering_newtype() {
+    let store = hook_store::Store::collect();
+
+    assert_eq!(
+        store.ordering_bounds(),
+        Some((&HookOrdering(1), &HookOrdering(5)))
+    );
+    assert_eq!(store.ordering(&HookOrdering(5)).unwrap().count(), 1);
+}
@@ -0,0 +1,67 @@
+use stain::{create_stain, stain, Store};
+
+trait Hook {}
+
+create_stain! {
+    trait Hook;
+    store: mod hook_store;
+}
+
+#[derive(Default)]
+struct Audit;
+impl Hook for Audit {}
+
+#[derive(Default)]
+struct Trace;
+impl Hook for Trace {}
+
+// Branch: the same type registered twice
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 0;
+}
+
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 1;
+}
+
+stain! {
+    store: hook_store;
+    item: Trace;
+    ordering: 2;
+}
+
+#[test]
+fn test_plugin_key() {
+    let store = hook_store::Store::collect();
+
+    let keys: Vec<_> = store.keys_of::<Audit>().collect();
+    assert_eq!(keys.len(), 2);
+    assert_ne!(keys[0], keys[1]);
+
+    // Each key resolves to its own registration, even the one
+    // dropped by deduplication.
+    let first = store.get_registration(keys[0]).unwrap();
+    let second = store.get_registration(keys[1]).unwrap();
+    assert_ne!(first.ordering(), second.ordering());
+    assert_ne!(first.location(), second.location());
+
+    // Only the kept registration resolves to a live implementation.
+    let kept = store.iter().next().unwrap();
+    let key = store.key_of(&kept);
+    assert!(keys.contains(&key));
+    assert_eq!(store.get_by_key(key).unwrap().ordering(), kept.ordering());
+
+    let dropped = keys.iter().copied().find(|other| *other != key).unwrap();
+    assert!(store.get_by_key(dropped).is_none());
+    assert!(store.get_registration(dropped).is_some());
+
+    // Keys stay valid across stores collected from the same slice.
+    let trace = store.keys_of::<Trace>().next().unwrap();
+    let audits = hook_store::Store::collect_filtered(|entry| *entry.ordering() < 2);
+    assert!(audits.get_by_key(key).is_some());
+    assert!(audits.get_by_key(trace).is_none());
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use stain::{create_stain, stain_shared, Store};
+
+trait Record {
+    fn record(&self);
+}
+
+trait Report {
+    fn count(&self) -> usize;
+}
+
+create_stain! {
+    trait Record;
+    store: mod record_store;
+}
+
+create_stain! {
+    trait Report;
+    store: mod report_store;
+}
+
+// Branch: one instance shared by two stores
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+impl Record for Counter {
+    fn record(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Report for Counter {
+    fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+stain_shared! {
+    item: Counter;
+    store: record_store;
+    ordering: 0;
+    store: report_store;
+    name: "counter";
+    ordering: 5;
+    tiebreak: 2;
+    meta: { "unit" => "calls" };
+}
+
+#[test]
+fn test_shared() {
+    let records = record_store::Store::collect();
+    let reports = report_store::Store::collect();
+
+    records.iter().for_each(|entry| entry.record());
+    records.iter().for_each(|entry| entry.record());
+
+    let report = reports.iter().next().unwrap();
+    assert_eq!(report.count(), 2);
+    assert_eq!(report.ordering(), &5);
+    assert_eq!(report.name(), "counter");
+    assert_eq!(report.tiebreak(), 2);
+    assert_eq!(report.meta(), &[("unit", "calls")]);
+
+    // Options only apply to the store they're given for.
+    let record = records.iter().next().unwrap();
+    assert_eq!(record.name(), "Counter");
+    assert!(record.meta().is_empty());
+}
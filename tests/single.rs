@@ -0,0 +1,51 @@
+use stain::{create_stain, stain, SingleError, Store};
+
+trait Setting {}
+
+create_stain! {
+    trait Setting;
+    store: mod settings;
+}
+
+#[derive(Default)]
+struct Theme;
+impl Setting for Theme {}
+
+#[derive(Default)]
+struct Locale;
+impl Setting for Locale {}
+
+#[derive(Default)]
+struct Unregistered;
+impl Setting for Unregistered {}
+
+stain! {
+    store: settings;
+    item: Theme;
+    ordering: 0;
+}
+
+// Branch: the same type registered twice
+stain! {
+    store: settings;
+    item: Locale;
+    ordering: 0;
+}
+
+stain! {
+    store: settings;
+    item: Locale;
+    ordering: 1;
+}
+
+#[test]
+fn test_single() {
+    let store = settings::Store::collect();
+
+    assert!(store.single::<Theme>().is_ok());
+    assert_eq!(store.single::<Locale>().err(), Some(SingleError::Multiple(2)));
+    assert_eq!(
+        store.single::<Unregistered>().err(),
+        Some(SingleError::Missing)
+    );
+}
@@ -0,0 +1,27 @@
+use stain::{create_stain, stain, Store};
+
+trait Hook {}
+
+// Branch: doc comment and attributes on the store declaration
+create_stain! {
+    trait Hook;
+    /// Hooks run before each request.
+    #[allow(dead_code)]
+    store: pub(crate) mod hook_store;
+}
+
+#[derive(Default)]
+struct Audit;
+impl Hook for Audit {}
+
+stain! {
+    store: hook_store;
+    item: Audit;
+    ordering: 0;
+}
+
+#[test]
+fn test_store_attrs() {
+    let store = hook_store::Store::collect();
+    assert_eq!(store.iter().count(), 1);
+}
@@ -0,0 +1,35 @@
+use stain::{create_stain, stain, stain_assert_impl, Store};
+
+trait Config {
+    fn key(&self) -> &'static str;
+}
+
+// Branch: custom store struct name
+create_stain! {
+    trait Config;
+    store: pub(crate) mod config as ConfigRegistry;
+}
+
+#[derive(Default)]
+struct Port;
+
+impl Config for Port {
+    fn key(&self) -> &'static str {
+        "port"
+    }
+}
+
+stain! {
+    store: config;
+    item: Port;
+    ordering: 0;
+}
+
+stain_assert_impl!(config, Port);
+
+#[test]
+fn test_store_name() {
+    let registry = config::ConfigRegistry::collect();
+
+    assert_eq!(registry.iter().next().unwrap().key(), "port");
+}
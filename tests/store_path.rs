@@ -0,0 +1,39 @@
+use stain::{stain, Store};
+
+mod plugins {
+    pub mod registry {
+        use stain::create_stain;
+
+        pub trait Hook {
+            fn run(&self) -> &'static str;
+        }
+
+        create_stain! {
+            trait Hook;
+            store: pub mod hook_store;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Audit;
+
+impl plugins::registry::Hook for Audit {
+    fn run(&self) -> &'static str {
+        "audit"
+    }
+}
+
+// Branch: store given as a multi-segment path
+stain! {
+    store: crate::plugins::registry::hook_store;
+    item: Audit;
+    ordering: 0;
+}
+
+#[test]
+fn test_store_path() {
+    let store = plugins::registry::hook_store::Store::collect();
+
+    assert_eq!(store.iter().next().unwrap().run(), "audit");
+}
@@ -0,0 +1,54 @@
+use stain::{create_stain, stain_shared, Store};
+
+trait Base {
+    fn id(&self) -> &'static str;
+}
+
+trait Plugin: Base {
+    fn run(&self) -> String {
+        format!("running {}", self.id())
+    }
+}
+
+create_stain! {
+    trait Base;
+    store: mod base_store;
+}
+
+create_stain! {
+    trait Plugin;
+    store: mod plugin_store;
+}
+
+#[derive(Default)]
+struct Audit;
+
+impl Base for Audit {
+    fn id(&self) -> &'static str {
+        "audit"
+    }
+}
+
+impl Plugin for Audit {}
+
+// Branch: a derived trait's implementation also registered in the base store
+stain_shared! {
+    item: Audit;
+    store: plugin_store;
+    ordering: 0;
+    store: base_store;
+    ordering: 0;
+}
+
+#[test]
+fn test_supertrait() {
+    let plugins = plugin_store::Store::collect();
+    let bases = base_store::Store::collect();
+
+    assert_eq!(plugins.iter().next().unwrap().run(), "running audit");
+    assert_eq!(bases.iter().next().unwrap().id(), "audit");
+    assert!(std::ptr::eq(
+        &*plugins.concrete::<Audit>().unwrap(),
+        &*bases.concrete::<Audit>().unwrap(),
+    ));
+}
@@ -0,0 +1,51 @@
+use stain::{create_stain, stain, Store};
+
+trait Step {}
+
+create_stain! {
+    trait Step;
+    store: mod step_store;
+}
+
+// Branch: tiebreak overrides name order within an ordering
+#[derive(Default)]
+struct Alpha;
+impl Step for Alpha {}
+
+stain! {
+    store: step_store;
+    item: Alpha;
+    ordering: 0;
+    tiebreak: 2;
+}
+
+#[derive(Default)]
+struct Beta;
+impl Step for Beta {}
+
+stain! {
+    store: step_store;
+    item: Beta;
+    ordering: 0;
+    tiebreak: 1;
+    meta: { "desc" => "runs before Alpha" };
+}
+
+// Branch: no tiebreak defaults to 0
+#[derive(Default)]
+struct Gamma;
+impl Step for Gamma {}
+
+stain! {
+    store: step_store;
+    item: Gamma;
+    ordering: 0;
+}
+
+#[test]
+fn test_tiebreak() {
+    let store = step_store::Store::collect();
+    let names: Vec<_> = store.iter().map(|entry| entry.name()).collect();
+
+    assert_eq!(names, ["Gamma", "Beta", "Alpha"]);
+}
@@ -0,0 +1,56 @@
+use stain::{create_stain, stain, Store};
+
+trait Handler {
+    fn id(&self) -> &'static str;
+}
+
+create_stain! {
+    trait Handler;
+    store: mod handlers;
+}
+
+#[derive(Default)]
+struct Primary;
+
+impl Handler for Primary {
+    fn id(&self) -> &'static str {
+        "primary"
+    }
+}
+
+#[derive(Default)]
+struct Fallback;
+
+impl Handler for Fallback {
+    fn id(&self) -> &'static str {
+        "fallback"
+    }
+}
+
+stain! {
+    store: handlers;
+    item: Primary;
+    ordering: 2;
+}
+
+stain! {
+    store: handlers;
+    item: Fallback;
+    ordering: 5;
+}
+
+#[test]
+fn test_top_non_empty() {
+    let mut store = handlers::Store::collect();
+
+    let (ordering, tier) = store.top_non_empty().unwrap();
+    assert_eq!(*ordering, 2);
+    assert_eq!(tier.iter().map(|entry| entry.id()).collect::<Vec<_>>(), ["primary"]);
+
+    store.retain(|entry| entry.id() != "primary");
+    let (ordering, _) = store.top_non_empty().unwrap();
+    assert_eq!(*ordering, 5);
+
+    store.retain(|_| false);
+    assert!(store.top_non_empty().is_none());
+}
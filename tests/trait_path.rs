@@ -0,0 +1,60 @@
+use stain::{create_stain, stain, Store};
+
+mod upstream {
+    pub trait Plugin {
+        fn label(&self) -> &'static str;
+    }
+
+    pub trait Codec<T> {
+        fn encode(&self, value: T) -> String;
+    }
+}
+
+// Branch: trait given by path
+create_stain! {
+    trait upstream::Plugin;
+    store: mod plugins;
+}
+
+// Branch: generic trait given by path
+create_stain! {
+    trait upstream::Codec;
+    type u32;
+    store: mod codecs;
+}
+
+#[derive(Default)]
+struct Local;
+
+impl upstream::Plugin for Local {
+    fn label(&self) -> &'static str {
+        "local"
+    }
+}
+
+impl upstream::Codec<u32> for Local {
+    fn encode(&self, value: u32) -> String {
+        value.to_string()
+    }
+}
+
+stain! {
+    store: plugins;
+    item: Local;
+    ordering: 0;
+}
+
+stain! {
+    store: codecs;
+    item: Local;
+    ordering: 0;
+}
+
+#[test]
+fn test_trait_path() {
+    let plugins = plugins::Store::collect();
+    let codecs = codecs::Store::collect();
+
+    assert_eq!(plugins.iter().next().unwrap().label(), "local");
+    assert_eq!(codecs.iter().next().unwrap().encode(7), "7");
+}
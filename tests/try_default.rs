@@ -0,0 +1,100 @@
+use stain::{create_stain, stain, Store, TryDefault};
+
+trait Backend {
+    fn url(&self) -> &str;
+}
+
+create_stain! {
+    trait Backend;
+    store: mod backends;
+}
+
+struct Local {
+    url: String,
+}
+
+impl TryDefault for Local {
+    fn try_default() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self {
+            url: "http://localhost".to_string(),
+        })
+    }
+}
+
+impl Backend for Local {
+    fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+struct Mirror;
+
+impl TryDefault for Mirror {
+    fn try_default() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+}
+
+impl Backend for Mirror {
+    fn url(&self) -> &str {
+        "http://mirror"
+    }
+}
+
+struct Remote;
+
+impl TryDefault for Remote {
+    fn try_default() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Err("`REMOTE_URL` isn't set".into())
+    }
+}
+
+impl Backend for Remote {
+    fn url(&self) -> &str {
+        unreachable!()
+    }
+}
+
+// Branch: fallible construction
+stain! {
+    store: backends;
+    item: Local;
+    ordering: 0;
+    construct: try_default;
+}
+
+stain! {
+    store: backends;
+    item: Remote;
+    ordering: 1;
+    construct: try_default;
+}
+
+// Branch: fallible construction with options
+stain! {
+    store: backends;
+    item: Mirror;
+    name: "mirror";
+    ordering: 0;
+    tiebreak: 1;
+    meta: { "region" => "eu" };
+    construct: try_default;
+}
+
+#[test]
+fn test_try_default() {
+    let store = backends::Store::collect();
+    let error = store.init_all().unwrap_err();
+
+    assert_eq!(error.name(), "Remote");
+    assert_eq!(
+        error.message(),
+        Some("failed to construct `Remote`: `REMOTE_URL` isn't set")
+    );
+    assert_eq!(store.get_by_name("Local").unwrap().url(), "http://localhost");
+
+    let mirror = store.get_by_name("mirror").unwrap();
+    assert_eq!(mirror.url(), "http://mirror");
+    assert_eq!(mirror.tiebreak(), 1);
+    assert_eq!(mirror.meta(), &[("region", "eu")]);
+}
@@ -0,0 +1,56 @@
+use std::marker::PhantomData;
+
+use stain::{create_stain, stain, Store};
+
+trait Adapt {}
+
+create_stain! {
+    trait Adapt;
+    store: mod adapt_store;
+}
+
+#[derive(Default)]
+struct Adapter<T>(PhantomData<T>);
+impl<T: Send + Sync + 'static> Adapt for Adapter<T> {}
+
+// Branch: generic instantiation named by `type_name`
+stain! {
+    store: adapt_store;
+    item: Adapter<u8>;
+    name: type_name;
+    ordering: 0;
+}
+
+// Branch: generic instantiation named by its tokens
+stain! {
+    store: adapt_store;
+    item: Adapter<u16>;
+    ordering: 1;
+}
+
+// Branch: generic instantiation named by a literal
+stain! {
+    store: adapt_store;
+    item: Adapter<u32>;
+    name: "adapter";
+    ordering: 2;
+}
+
+#[test]
+fn test_type_name() {
+    let store = adapt_store::Store::collect();
+    let mut store_iter = store.iter();
+
+    let by_type_name = store_iter.next().unwrap();
+    assert!(by_type_name.name().ends_with("Adapter<u8>"));
+    assert_eq!(
+        store.concrete::<Adapter<u8>>().unwrap().name(),
+        by_type_name.name()
+    );
+
+    let by_tokens = store_iter.next().unwrap();
+    assert!(by_tokens.name().contains("u16"));
+
+    let by_literal = store_iter.next().unwrap();
+    assert_eq!(by_literal.name(), "adapter");
+}
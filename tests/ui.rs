@@ -0,0 +1,96 @@
+//! Checks the compile errors of the files in `tests/ui` against their
+//! `.stderr` files. Set `STAIN_BLESS=1` to overwrite them.
+//!
+//! Diagnostics change between compiler releases, so the `.stderr` files
+//! are only compared on the toolchain they were blessed with. Other
+//! toolchains only check that each case fails to compile.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// Builds `stain` into a target directory of its own, and returns its rlib
+// along with the directory of its dependencies.
+fn build_stain() -> (PathBuf, PathBuf) {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["build", "--lib", "--quiet", "--message-format", "json"])
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rlib = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter(|message| message["target"]["name"] == "stain")
+        .flat_map(|message| message["filenames"].as_array().cloned().unwrap_or_default())
+        .filter_map(|filename| filename.as_str().map(PathBuf::from))
+        .find(|filename| filename.extension().is_some_and(|extension| extension == "rlib"))
+        .expect("`stain` builds an rlib");
+    // The rlib is uplifted out of `deps`, where its dependencies stay.
+    let deps = rlib.parent().unwrap().join("deps");
+
+    (rlib, deps)
+}
+
+#[rustversion::stable(1.95)]
+const COMPARE_STDERR: bool = true;
+
+#[rustversion::not(stable(1.95))]
+const COMPARE_STDERR: bool = false;
+
+#[test]
+fn ui() {
+    let (rlib, deps) = build_stain();
+    let ui = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("ui");
+
+    let mut cases = fs::read_dir(&ui)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect::<Vec<_>>();
+    cases.sort();
+
+    for case in cases {
+        let file_name = case.file_name().unwrap();
+
+        // Compiled from within `tests/ui`, so diagnostics name the bare
+        // file on every platform.
+        let output = Command::new(env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+            .current_dir(&ui)
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata"])
+            .args(["--error-format", "short", "--color", "never"])
+            .arg("--out-dir")
+            .arg(env!("CARGO_TARGET_TMPDIR"))
+            .arg("-L")
+            .arg(format!("dependency={}", deps.display()))
+            .arg("--extern")
+            .arg(format!("stain={}", rlib.display()))
+            .arg(file_name)
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "{} compiled", case.display());
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        let expected = case.with_extension("stderr");
+
+        if env::var_os("STAIN_BLESS").is_some() {
+            fs::write(&expected, &stderr).unwrap();
+        } else if COMPARE_STDERR {
+            assert_eq!(stderr, fs::read_to_string(&expected).unwrap(), "{}", case.display());
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use stain::create_stain;
+
+pub trait Hook {}
+
+// Known options, in the wrong order.
+create_stain! {
+    store: mod hook_store;
+    trait Hook;
+}
@@ -0,0 +1,9 @@
+use stain::create_stain;
+
+pub trait Hook {}
+
+create_stain! {
+    trait Hook;
+    ordering: i32;
+    stor: mod hook_store;
+}
@@ -0,0 +1,13 @@
+use stain::{create_stain, stain_assert_impl};
+
+pub trait Hook {}
+
+create_stain! {
+    trait Hook;
+    store: mod hook_store;
+}
+
+// Doesn't implement `Hook`.
+pub struct Audit;
+
+stain_assert_impl!(hook_store, Audit);
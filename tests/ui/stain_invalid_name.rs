@@ -0,0 +1,30 @@
+use stain::{create_stain, stain};
+
+pub trait Hook {}
+
+create_stain! {
+    trait Hook;
+    store: mod hook_store;
+}
+
+#[derive(Default)]
+pub struct Audit;
+impl Hook for Audit {}
+
+stain! {
+    store: hook_store;
+    item: Audit;
+    name: bogus;
+    ordering: 0;
+}
+
+#[derive(Default)]
+pub struct Trace;
+impl Hook for Trace {}
+
+stain! {
+    store: hook_store;
+    item: Trace;
+    name: TypeName;
+    ordering: 1;
+}
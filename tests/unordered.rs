@@ -0,0 +1,52 @@
+use stain::{create_stain, stain, Store};
+
+trait Task {}
+
+create_stain! {
+    trait Task;
+    store: mod task_store;
+}
+
+#[derive(Default)]
+struct Migrate;
+impl Task for Migrate {}
+
+stain! {
+    store: task_store;
+    item: Migrate;
+    ordering: 0;
+}
+
+// Branch: implementations without a meaningful relative order
+#[derive(Default)]
+struct WarmCache;
+impl Task for WarmCache {}
+
+stain! {
+    store: task_store;
+    item: WarmCache;
+    ordering: 1;
+    unordered: true;
+}
+
+#[derive(Default)]
+struct Ping;
+impl Task for Ping {}
+
+stain! {
+    store: task_store;
+    item: Ping;
+    ordering: 1;
+    unordered: true;
+}
+
+#[test]
+fn test_unordered() {
+    let store = task_store::Store::collect();
+
+    let unordered: Vec<_> = store.unordered().map(|entry| entry.name()).collect();
+    assert_eq!(unordered, ["Ping", "WarmCache"]);
+
+    let all: Vec<_> = store.iter().map(|entry| entry.name()).collect();
+    assert_eq!(all, ["Migrate", "Ping", "WarmCache"]);
+}